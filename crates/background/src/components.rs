@@ -12,6 +12,11 @@ pub struct Background {
     pub directional_light: f32,
 }
 
+/// Marker component for the lighting and ground spawned as children of a background, so they can be re-spawned when
+/// the background changes.
+#[derive(Component)]
+pub(crate) struct BackgroundPart;
+
 #[derive(Bundle)]
 pub struct BackgroundBundle {
     pub background: Background,
@@ -11,8 +11,14 @@ pub struct BackgroundPlugin;
 
 impl Plugin for BackgroundPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, setup_new_backgrounds.in_set(SetupSet::RigidBody))
-            .register_saveable::<Background>();
+        app.add_systems(
+            Update,
+            (
+                (setup_new_backgrounds, rebuild_changed_backgrounds).in_set(SetupSet::RigidBody),
+                update_garden_bounds,
+            ),
+        )
+        .register_saveable::<Background>();
     }
 }
 
@@ -25,15 +31,6 @@ fn setup_new_backgrounds(
     for (entity, background, disabled) in query.iter() {
         info!("[Background] ==> Setup new background");
 
-        // Set up the clear color resource
-        commands.insert_resource(ClearColor(background.background_color));
-
-        // Enable ambient lighting
-        commands.insert_resource(AmbientLight {
-            brightness: background.ambient_light,
-            ..default()
-        });
-
         let mut cmds = commands.entity(entity);
 
         // Add required components to the background
@@ -49,9 +46,102 @@ fn setup_new_backgrounds(
             cmds.insert((Saveable, DespawnOnLoad));
         }
 
-        // Spawn directional lighting as a child of the Background
-        cmds.with_children(|p| {
-            p.spawn(DirectionalLightBundle {
+        spawn_background_parts(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            entity,
+            background,
+            disabled,
+        );
+    }
+}
+
+/// System that re-spawns the lighting and ground of backgrounds whose `Background` changed, e.g. when it is edited in
+/// the inspector.
+///
+/// NOTE: Attachables placed on the background are not affected.
+#[allow(clippy::type_complexity)]
+fn rebuild_changed_backgrounds(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    query: Query<(Entity, Ref<Background>, Option<&Children>, Has<Disabled>)>,
+    part_query: Query<(), With<BackgroundPart>>,
+) {
+    for (entity, background, children, disabled) in query.iter() {
+        // New backgrounds are handled by `setup_new_backgrounds`
+        if !background.is_changed() || background.is_added() {
+            continue;
+        }
+        info!("[Background] ==> Rebuild changed background");
+
+        for child in children.into_iter().flatten() {
+            if part_query.contains(*child) {
+                commands.entity(*child).despawn_recursive();
+            }
+        }
+        spawn_background_parts(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            entity,
+            &background,
+            disabled,
+        );
+    }
+}
+
+/// System that keeps the `GardenBounds` resource in sync with the size of the ground.
+fn update_garden_bounds(
+    query: Query<(Ref<Background>, &Transform), Without<Disabled>>,
+    mut removed: RemovedComponents<Background>,
+    mut garden_bounds: ResMut<GardenBounds>,
+) {
+    for (background, transform) in query.iter() {
+        if background.is_changed() {
+            let new_bounds = GardenBounds(Some(Rect::from_center_size(
+                transform.translation.xz(),
+                Vec2::splat(background.size),
+            )));
+            if *garden_bounds != new_bounds {
+                info!("[Background] ==> Update garden bounds");
+                *garden_bounds = new_bounds;
+            }
+        }
+    }
+
+    // The ground is unbounded while there is no background, e.g. while loading a level
+    if removed.read().count() > 0 && query.is_empty() {
+        garden_bounds.0 = None;
+    }
+}
+
+/// Utility that sets up the lighting resources, and spawns the lighting and ground mesh as children of a background.
+fn spawn_background_parts(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<StandardMaterial>,
+    entity: Entity,
+    background: &Background,
+    disabled: bool,
+) {
+    // Set up the clear color resource
+    commands.insert_resource(ClearColor(background.background_color));
+
+    // Enable ambient lighting
+    commands.insert_resource(AmbientLight {
+        brightness: background.ambient_light,
+        ..default()
+    });
+
+    let mut cmds = commands.entity(entity);
+
+    // Spawn directional lighting as a child of the Background
+    cmds.with_children(|p| {
+        p.spawn((
+            BackgroundPart,
+            DirectionalLightBundle {
                 directional_light: DirectionalLight {
                     shadows_enabled: true,
                     illuminance: background.directional_light,
@@ -60,24 +150,25 @@ fn setup_new_backgrounds(
                 transform: Transform::from_translation(Vec3::ZERO)
                     .looking_at(Vec3::new(-0.5, -1.0, -0.5), Vec3::Y),
                 ..default()
-            });
-        });
-
-        // Spawn the ground mesh as a child of the background
-        cmds.with_children(|p| {
-            let mut mesh_cmds = p.spawn((
-                PbrBundle {
-                    mesh: meshes.add(shape::Plane::from_size(background.size).into()),
-                    material: materials.add(background.ground_color.into()),
-                    ..default()
-                },
-                FamilyChild(entity),
-            ));
-            if disabled {
-                mesh_cmds.insert(TransparentMaterial);
-            } else {
-                mesh_cmds.insert(Pickable);
-            }
-        });
-    }
+            },
+        ));
+    });
+
+    // Spawn the ground mesh as a child of the background
+    cmds.with_children(|p| {
+        let mut mesh_cmds = p.spawn((
+            BackgroundPart,
+            PbrBundle {
+                mesh: meshes.add(shape::Plane::from_size(background.size).into()),
+                material: materials.add(background.ground_color.into()),
+                ..default()
+            },
+            FamilyChild(entity),
+        ));
+        if disabled {
+            mesh_cmds.insert(TransparentMaterial);
+        } else {
+            mesh_cmds.insert(Pickable);
+        }
+    });
 }
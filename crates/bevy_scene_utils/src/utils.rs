@@ -24,10 +24,11 @@ fn normalize_axes(mut axes: Vec3) -> Vec3 {
 pub fn center_entities_in_scene(scene: &mut Scene, mut axes: Vec3) {
     axes = normalize_axes(axes);
 
-    // define a query that only returns the transforms of physics bodies in the scene
+    // define a query that only returns the transforms of top-level physics bodies in the scene. Physics bodies with a
+    // parent, such as objects in a `Group`, are positioned relative to their parent and move with it.
     let mut query = scene
         .world
-        .query_filtered::<&mut Transform, With<PhysicsBody>>();
+        .query_filtered::<&mut Transform, (With<PhysicsBody>, Without<Parent>)>();
 
     // calculate the center by averaging all positions in the scene world
     let mut center = Vec3::ZERO;
@@ -158,14 +159,41 @@ pub fn saveable_scene_from_entity(
     entity: Entity,
     include_family: bool,
     include_children_components: bool,
+) -> DynamicScene {
+    saveable_scene_from_entities(
+        world,
+        &[entity],
+        include_family,
+        include_children_components,
+    )
+}
+
+/// Create a `DynamicScene` from multiple entities in a world. Entities shared between families are only included once.
+///
+/// # Panics
+///
+/// Panics if the world does not contain `AppTypeRegistry` or `SaveableRegistry` resources.
+pub fn saveable_scene_from_entities(
+    world: &mut World,
+    entities: &[Entity],
+    include_family: bool,
+    include_children_components: bool,
 ) -> DynamicScene {
     // get all children
-    let entities_to_save = if include_family {
-        get_entity_family_from_world(world, entity)
-    } else {
-        // NOTE: Does not check for presence of `Saveable` component
-        vec![entity]
-    };
+    let mut entities_to_save: Vec<Entity> = Vec::new();
+    for &entity in entities.iter() {
+        let family = if include_family {
+            get_entity_family_from_world(world, entity)
+        } else {
+            // NOTE: Does not check for presence of `Saveable` component
+            vec![entity]
+        };
+        for entity in family {
+            if !entities_to_save.contains(&entity) {
+                entities_to_save.push(entity);
+            }
+        }
+    }
     info!("creating scene from {} entities", entities_to_save.len());
 
     // define a scene filter which only includes types registered in the `SaveableRegistry`
@@ -222,6 +250,8 @@ pub fn scene_from_world_dynamic_scene(
 /// Utility that writes a dynamic scene to the world, and accepts a closure than gives mutable world acccess to each
 /// spawned entity.
 ///
+/// Returns the list of entities that were spawned.
+///
 /// # Examples:
 ///
 /// ```ignore
@@ -237,7 +267,7 @@ pub fn write_dynamic_scene_to_world(
     dynamic_scene: &DynamicScene,
     world: &mut World,
     update_fn: impl Fn(&mut EntityWorldMut<'_>),
-) -> Result<(), String> {
+) -> Result<Vec<Entity>, String> {
     let mut entity_map = HashMap::<Entity, Entity>::new();
     dynamic_scene
         .write_to_world(world, &mut entity_map)
@@ -250,7 +280,7 @@ pub fn write_dynamic_scene_to_world(
         }
     }
 
-    Ok(())
+    Ok(entity_map.values().copied().collect())
 }
 
 /// Utility that writes a dynamic scene asset to the world from it's handle, and accepts a closure than gives mutable
@@ -261,7 +291,7 @@ pub fn write_dynamic_scene_asset_to_world(
     dynamic_scene_id: impl Into<AssetId<DynamicScene>>,
     world: &mut World,
     update_fn: impl Fn(&mut EntityWorldMut<'_>),
-) -> Result<(), String> {
+) -> Result<Vec<Entity>, String> {
     world.resource_scope(|world, scenes: Mut<Assets<DynamicScene>>| {
         let dynamic_scene = scenes
             .get(dynamic_scene_id)
@@ -0,0 +1,137 @@
+use bevy::prelude::*;
+
+use editor::prelude::*;
+use game_effects::selected::Selection;
+use game_state::prelude::*;
+use save::prelude::Saveable;
+
+use crate::commands::{CaptureObjectsToClipboard, ImportClipboard};
+
+/// Plugin which handles copying, cutting and pasting objects.
+///
+/// Copying captures the selected objects to a scene and stores it in the `Clipboard` resource. Pasting starts placing
+/// the clipboard scene with the prefab tool.
+///
+/// The clipboard can also be exported to the system clipboard as RON text, and imported from it again later, which
+/// allows sharing objects between sessions. If the system clipboard isn't available, `CLIPBOARD_FILENAME` is used
+/// instead.
+pub struct ClipboardPlugin;
+
+impl Plugin for ClipboardPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Clipboard>()
+            .add_event::<ClipboardEvent>()
+            .add_systems(
+                Update,
+                handle_clipboard_events.run_if(on_event::<ClipboardEvent>()),
+            );
+
+        #[cfg(not(target_arch = "wasm32"))]
+        app.init_resource::<SystemClipboard>();
+    }
+}
+
+/// File the clipboard is exported to and imported from when the system clipboard isn't available, relative to the
+/// asset folder.
+pub const CLIPBOARD_FILENAME: &str = "clipboard/clipboard.scn.ron";
+
+/// Resource containing the scene of the last copied objects.
+#[derive(Resource, Default)]
+pub struct Clipboard(pub Option<Handle<DynamicScene>>);
+
+/// Resource containing the system clipboard, which is opened the first time it is used.
+///
+/// NOTE: The clipboard is kept open because on Linux the exported text is hosted by the application that set it, and is
+/// lost when the clipboard is closed.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Resource, Default)]
+pub(crate) struct SystemClipboard(Option<arboard::Clipboard>);
+
+#[cfg(not(target_arch = "wasm32"))]
+impl SystemClipboard {
+    /// Returns the system clipboard, opening it if it isn't open yet.
+    pub(crate) fn get(&mut self) -> Result<&mut arboard::Clipboard, arboard::Error> {
+        let clipboard = match self.0.take() {
+            Some(clipboard) => clipboard,
+            None => arboard::Clipboard::new()?,
+        };
+        Ok(self.0.insert(clipboard))
+    }
+}
+
+/// Event used to trigger clipboard actions.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClipboardEvent {
+    /// Copy the selected objects to the clipboard
+    Copy,
+    /// Copy the selected objects to the clipboard and de-spawn them
+    Cut,
+    /// Start placing the objects in the clipboard
+    Paste,
+    /// Copy the selected objects to the clipboard and export them to the system clipboard as RON text
+    Export,
+    /// Import RON text from the system clipboard into the clipboard and start placing it
+    Import,
+}
+
+/// Utility that returns the prefab tool used to paste a scene.
+pub(crate) fn paste_tool(scene: Handle<DynamicScene>) -> Tool {
+    Tool::Prefab(PrefabConfig {
+        name: "Clipboard".to_string(),
+        // NOTE: The captured scene keeps the `PrefabSource` of the original objects
+        key: None,
+        tool_type: PrefabToolType::Object,
+        scene,
+        initial_scale: 1.,
+        scaling: Some(ToolScaling { min: 0.5, max: 2.0 }),
+        allow_overlap: false,
+        tags: Vec::new(),
+    })
+}
+
+/// System that handles `ClipboardEvent` events.
+///
+/// NOTE: `Locked` objects, and objects on a locked layer, can be copied but not cut.
+#[allow(clippy::too_many_arguments)]
+fn handle_clipboard_events(
+    mut commands: Commands,
+    mut events: EventReader<ClipboardEvent>,
+    selection: Res<Selection>,
+    clipboard: Res<Clipboard>,
+    valid_target_query: Query<(), (With<Saveable>, Without<StaticLocked>)>,
+    locked_query: Query<(), With<Locked>>,
+    layers: Layers,
+    mut push_tool_writer: EventWriter<PushToolEvent>,
+) {
+    for event in events.read() {
+        match event {
+            ClipboardEvent::Copy | ClipboardEvent::Cut | ClipboardEvent::Export => {
+                let entities: Vec<Entity> = selection
+                    .entities()
+                    .iter()
+                    .copied()
+                    .filter(|entity| valid_target_query.contains(*entity))
+                    .filter(|entity| {
+                        *event != ClipboardEvent::Cut
+                            || !(locked_query.contains(*entity)
+                                || layers.of_object(*entity).is_some_and(|layer| layer.locked))
+                    })
+                    .collect();
+                if entities.is_empty() {
+                    warn!("[Clipboard] ==> Nothing selected to copy");
+                    continue;
+                }
+                commands.add(CaptureObjectsToClipboard {
+                    entities,
+                    cut: *event == ClipboardEvent::Cut,
+                    export: *event == ClipboardEvent::Export,
+                });
+            }
+            ClipboardEvent::Paste => match clipboard.0.as_ref() {
+                Some(scene) => push_tool_writer.send(PushToolEvent(paste_tool(scene.clone()))),
+                None => warn!("[Clipboard] ==> Clipboard is empty"),
+            },
+            ClipboardEvent::Import => commands.add(ImportClipboard),
+        }
+    }
+}
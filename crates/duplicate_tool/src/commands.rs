@@ -1,8 +1,22 @@
 use bevy::{ecs::system::Command, prelude::*};
 
 use bevy_scene_utils::*;
+use editor::prelude::DespawnEntityAndRelations;
+use game_effects::selected::SelectEvent;
 
-use crate::types::CaptureObjectToSceneResult;
+use crate::{
+    clipboard::{Clipboard, CLIPBOARD_FILENAME},
+    types::CaptureObjectToSceneResult,
+};
+
+#[cfg(not(target_arch = "wasm32"))]
+use {
+    crate::clipboard::{paste_tool, SystemClipboard},
+    bevy::tasks::IoTaskPool,
+    editor::prelude::PushToolEvent,
+    save::prelude::ensure_directory_exists_for_filename,
+    std::{fs::File, io::Write},
+};
 
 /// Command that extracts a scene from an entity and emits a `PickSceneResult` event when done.
 ///
@@ -22,28 +36,191 @@ pub(crate) struct CaptureObjectToScene {
 
 impl Command for CaptureObjectToScene {
     fn apply(self, world: &mut World) {
-        // create a scene from the entity
-        let dynamic_scene = saveable_scene_from_entity(world, self.entity, true, true);
-        let type_registry = world.resource::<AppTypeRegistry>();
-        let scene = dynamic_scene_to_scene(&dynamic_scene, type_registry);
-
-        if let Ok(mut scene) = scene {
-            // center objects in the scene along the XZ axes.
-            // NOTE: The XZ axes are specific to this app, where objects are placed on a fixed plane. If this were a
-            // 2D platformer we might center them along the XY axes. If objects could be placed anywhere along the Y
-            // axis, we would center them along all 3 axes.
-            center_entities_in_scene(&mut scene, Vec3::new(1.0, 0.0, 1.0));
-
-            // If using a physics engine, you should clear any `Velocity` components in the scene
-            // set_velocity_in_scene(&mut scene, Vec2::ZERO, 0.0);
-
-            // create a dynamic scene asset from the scene
-            let mut assets = world.resource_mut::<Assets<DynamicScene>>();
-            let dynamic_scene_handle = assets.add(DynamicScene::from_scene(&scene));
-
-            world.send_event(CaptureObjectToSceneResult(Ok(dynamic_scene_handle)));
-        } else if let Err(err) = scene {
-            world.send_event(CaptureObjectToSceneResult(Err(err)));
+        let result = capture_objects_to_scene(world, &[self.entity]);
+        world.send_event(CaptureObjectToSceneResult(result));
+    }
+}
+
+/// Command that captures objects to a scene and stores it in the `Clipboard`.
+///
+/// * `cut` - De-spawn the objects after capturing them.
+/// * `export` - Also copy the captured scene to the system clipboard as RON text, so it can be shared and imported in
+///   another session.
+///
+/// # Panics
+///
+/// Panics if the world does not contain `AppTypeRegistry`, `SaveableRegistry` or `Assets<DynamicScene>` resources.
+#[derive(Debug)]
+pub(crate) struct CaptureObjectsToClipboard {
+    pub entities: Vec<Entity>,
+    pub cut: bool,
+    pub export: bool,
+}
+
+impl Command for CaptureObjectsToClipboard {
+    fn apply(self, world: &mut World) {
+        let scene_handle = match capture_objects_to_scene(world, &self.entities) {
+            Ok(scene_handle) => scene_handle,
+            Err(err) => {
+                error!("[Clipboard] ==> Error capturing objects to scene: {err}");
+                return;
+            }
+        };
+        info!(
+            "[Clipboard] ==> captured {} objects, cut: {}, export: {}",
+            self.entities.len(),
+            self.cut,
+            self.export
+        );
+
+        if self.export {
+            export_scene(world, &scene_handle);
+        }
+        world.resource_mut::<Clipboard>().0 = Some(scene_handle);
+
+        if self.cut {
+            // NOTE: The delete event handler saves a single rollback for all de-spawned objects
+            for entity in self.entities.iter() {
+                world.send_event(DespawnEntityAndRelations(*entity));
+            }
+            world.send_event(SelectEvent(Vec::new()));
+        }
+    }
+}
+
+/// Command that reads a scene from the RON text in the system clipboard into the `Clipboard`, and starts placing it
+/// with the prefab tool. If the system clipboard isn't available, the scene is read from `CLIPBOARD_FILENAME` instead.
+///
+/// # Panics
+///
+/// Panics if the world does not contain `AppTypeRegistry` or `Assets<DynamicScene>` resources.
+#[derive(Debug)]
+pub(crate) struct ImportClipboard;
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Command for ImportClipboard {
+    fn apply(self, world: &mut World) {
+        let text = world
+            .resource_mut::<SystemClipboard>()
+            .get()
+            .and_then(|clipboard| clipboard.get_text());
+        let (serialized_scene, source) = match text {
+            Ok(text) => (text, "the system clipboard".to_string()),
+            Err(err) => {
+                let filename = format!("assets/{CLIPBOARD_FILENAME}");
+                warn!("[Clipboard] ==> Can't read the system clipboard, importing {filename} instead: {err}");
+                match std::fs::read_to_string(&filename) {
+                    Ok(serialized_scene) => (serialized_scene, filename),
+                    Err(err) => {
+                        error!("[Clipboard] ==> Error reading {filename}: {err}");
+                        return;
+                    }
+                }
+            }
+        };
+        let dynamic_scene =
+            match deserialize_scene(&serialized_scene, world.resource::<AppTypeRegistry>()) {
+                Ok(dynamic_scene) => dynamic_scene,
+                Err(err) => {
+                    error!("[Clipboard] ==> Error deserializing the scene in {source}: {err}");
+                    return;
+                }
+            };
+
+        info!("[Clipboard] ==> imported from {source}");
+        let scene_handle = world
+            .resource_mut::<Assets<DynamicScene>>()
+            .add(dynamic_scene);
+        world.resource_mut::<Clipboard>().0 = Some(scene_handle.clone());
+        world.send_event(PushToolEvent(paste_tool(scene_handle)));
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+/// An implementation of the import command for WASM, which does nothing currently because we need file system access.
+impl Command for ImportClipboard {
+    fn apply(self, _world: &mut World) {
+        info!(
+            "[Clipboard] ==> ignoring ImportClipboard on wasm target: assets/{CLIPBOARD_FILENAME}"
+        );
+    }
+}
+
+/// Utility that captures the hierarchies of entities to a scene, and adds it to the `DynamicScene` assets.
+///
+/// All entities in the scene are centered, so the scene can be placed with the prefab tool.
+fn capture_objects_to_scene(
+    world: &mut World,
+    entities: &[Entity],
+) -> Result<Handle<DynamicScene>, String> {
+    // create a scene from the entities
+    let dynamic_scene = saveable_scene_from_entities(world, entities, true, true);
+    let type_registry = world.resource::<AppTypeRegistry>();
+    let mut scene = dynamic_scene_to_scene(&dynamic_scene, type_registry)?;
+
+    // center objects in the scene along the XZ axes.
+    // NOTE: The XZ axes are specific to this app, where objects are placed on a fixed plane. If this were a
+    // 2D platformer we might center them along the XY axes. If objects could be placed anywhere along the Y
+    // axis, we would center them along all 3 axes.
+    center_entities_in_scene(&mut scene, Vec3::new(1.0, 0.0, 1.0));
+
+    // If using a physics engine, you should clear any `Velocity` components in the scene
+    // set_velocity_in_scene(&mut scene, Vec2::ZERO, 0.0);
+
+    // create a dynamic scene asset from the scene
+    let mut assets = world.resource_mut::<Assets<DynamicScene>>();
+    Ok(assets.add(DynamicScene::from_scene(&scene)))
+}
+
+/// Utility that copies a captured scene to the system clipboard as RON text. If the system clipboard isn't available,
+/// the scene is written to `CLIPBOARD_FILENAME` instead.
+#[cfg(not(target_arch = "wasm32"))]
+fn export_scene(world: &mut World, scene_handle: &Handle<DynamicScene>) {
+    let Some(dynamic_scene) = world.resource::<Assets<DynamicScene>>().get(scene_handle) else {
+        return;
+    };
+    let serialized_scene = match dynamic_scene.serialize_ron(world.resource::<AppTypeRegistry>()) {
+        Ok(serialized_scene) => serialized_scene,
+        Err(err) => {
+            error!("[Clipboard] ==> Error serializing scene: {err:?}");
+            return;
+        }
+    };
+
+    let filename = format!("assets/{CLIPBOARD_FILENAME}");
+    let result = world
+        .resource_mut::<SystemClipboard>()
+        .get()
+        .and_then(|clipboard| clipboard.set_text(serialized_scene.clone()));
+    match result {
+        Ok(()) => {
+            info!("[Clipboard] ==> exported to the system clipboard as RON text");
+            return;
         }
+        Err(err) => {
+            warn!("[Clipboard] ==> Can't use the system clipboard, exporting to {filename} instead: {err}")
+        }
+    }
+
+    if let Err(err) = ensure_directory_exists_for_filename(&filename) {
+        error!("[Clipboard] ==> {err}");
+        return;
     }
+    // Writing the scene to a new file. Using a task to avoid calling the filesystem APIs in a system as they are
+    // blocking
+    IoTaskPool::get()
+        .spawn(async move {
+            let result = File::create(&filename)
+                .and_then(|mut file| file.write_all(serialized_scene.as_bytes()));
+            if let Err(err) = result {
+                error!("[Clipboard] ==> Error writing {filename}: {err}");
+            }
+        })
+        .detach();
+}
+
+/// An implementation of exporting for WASM, which does nothing currently because we need file system access.
+#[cfg(target_arch = "wasm32")]
+fn export_scene(_world: &mut World, _scene_handle: &Handle<DynamicScene>) {
+    info!("[Clipboard] ==> ignoring export on wasm target: assets/{CLIPBOARD_FILENAME}");
 }
@@ -1,7 +1,8 @@
+mod clipboard;
 mod commands;
 mod plugin;
 mod types;
 
 pub mod prelude {
-    pub use crate::plugin::*;
+    pub use crate::{clipboard::*, plugin::*};
 }
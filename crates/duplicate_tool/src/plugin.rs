@@ -18,7 +18,7 @@ pub struct DuplicateToolPlugin;
 
 impl Plugin for DuplicateToolPlugin {
     fn build(&self, app: &mut App) {
-        app.add_state::<DuplicateToolState>()
+        app.add_editor_tool::<DuplicateToolState>()
             .add_event::<CaptureObjectToSceneResult>()
             .add_systems(OnEnter(DuplicateToolState::Active), setup_tool)
             .add_systems(
@@ -29,7 +29,6 @@ impl Plugin for DuplicateToolPlugin {
                 Update,
                 (
                     debug_state_changes::<DuplicateToolState>,
-                    handle_tool_change_events.run_if(on_event::<ToolChangedEvent>()),
                     handle_pick_scene_result_events
                         .run_if(on_event::<CaptureObjectToSceneResult>()),
                     handle_mouse_click
@@ -51,6 +50,12 @@ enum DuplicateToolState {
     Active,
 }
 
+impl EditorTool for DuplicateToolState {
+    const KEY: &'static str = "duplicate";
+    const NAME: &'static str = "Duplicate";
+    const ACTIVE: Self = DuplicateToolState::Active;
+}
+
 /// System that spawns the tool we enter the activated state.
 fn setup_tool(mut commands: Commands) {
     info!("[{TOOL_NAME}] ==> setup");
@@ -63,6 +68,7 @@ fn setup_tool(mut commands: Commands) {
 
 /// Handle click events, try select an entity and mark them as selected.
 #[allow(clippy::too_many_arguments)]
+#[allow(clippy::type_complexity)]
 fn handle_mouse_click(
     mut commands: Commands,
     mut raycast: Raycast,
@@ -70,8 +76,8 @@ fn handle_mouse_click(
     cursor_ray: Res<CursorRay>,
     query: Query<Entity, With<DuplicateTool>>,
     family_child_query: Query<&FamilyChild>,
-    pickable_query: Query<(), With<Pickable>>,
-    valid_target_query: Query<&Transform, (With<Saveable>, Without<StaticLocked>)>,
+    pickable_query: PickableQuery,
+    valid_target_query: Query<&Transform, (With<Saveable>, Without<StaticLocked>, Without<Locked>)>,
 ) {
     // STYLE: this reduces the nesting below
     if !buttons.just_pressed(MouseButton::Left) {
@@ -80,7 +86,7 @@ fn handle_mouse_click(
 
     for _ in query.iter() {
         // pick an entity from the raycast, and make sure it matches `valid_target_query` and does not contain a
-        // `StaticLocked` component.
+        // `StaticLocked` or `Locked` component.
         let target_entity = pick_entity_from_raycast(
             &cursor_ray,
             &mut raycast,
@@ -119,28 +125,17 @@ fn handle_pick_scene_result_events(
             Ok(scene_handle) => {
                 replace_tool_events.send(ReplaceToolEvent(Tool::Prefab(PrefabConfig {
                     name: "Object".to_string(),
+                    // NOTE: The captured scene keeps the `PrefabSource` of the original object, if it has one
+                    key: None,
                     tool_type: PrefabToolType::Object,
                     scene: scene_handle.clone(),
                     initial_scale: 1.,
                     scaling: Some(ToolScaling { min: 0.5, max: 2.0 }),
+                    allow_overlap: false,
+                    tags: Vec::new(),
                 })));
             }
             Err(err) => error!("[{TOOL_NAME}] ==> Error capturing object to scene: {err}"),
         }
     }
 }
-
-/// System that listens for `ToolChangedEvent` events and activates or de-activates the tool.
-fn handle_tool_change_events(
-    mut events: EventReader<ToolChangedEvent>,
-    mut next_state: ResMut<NextState<DuplicateToolState>>,
-    state: Res<State<DuplicateToolState>>,
-) {
-    for event in events.read() {
-        if let Tool::Duplicate = &event.0 {
-            next_state.set(DuplicateToolState::Active);
-        } else if *state.get() != DuplicateToolState::Disabled {
-            next_state.set(DuplicateToolState::Disabled);
-        }
-    }
-}
@@ -0,0 +1,33 @@
+use bevy::prelude::*;
+
+use crate::prelude::*;
+
+/// Extension trait that adds editor-related methods to Bevy's [`App`].
+pub trait AppEditorToolExt {
+    /// Add a tool to the editor. The tool is registered in the `ToolLibrary`, and its state is activated whenever the
+    /// tool becomes the current tool. See `EditorTool`.
+    fn add_editor_tool<T: EditorTool>(&mut self) -> &mut Self;
+}
+
+impl AppEditorToolExt for App {
+    fn add_editor_tool<T: EditorTool>(&mut self) -> &mut Self {
+        self.init_resource::<ToolLibrary>()
+            .add_state::<T>()
+            .add_systems(
+                Update,
+                handle_editor_tool_changes::<T>.run_if(on_event::<ToolChangedEvent>()),
+            );
+
+        let mut tool_library = self.world.resource_mut::<ToolLibrary>();
+        tool_library.register_tool(ToolInfo {
+            key: T::KEY.to_string(),
+            name: T::NAME.to_string(),
+            tool: T::tool(),
+        });
+        if T::DEFAULT {
+            tool_library.default_tool = Some(T::tool());
+        }
+
+        self
+    }
+}
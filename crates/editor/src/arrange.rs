@@ -0,0 +1,253 @@
+use bevy::{prelude::*, render::primitives::Aabb};
+use serde::{Deserialize, Serialize};
+
+use game_state::prelude::*;
+use save::prelude::*;
+
+use crate::prelude::{compute_object_bounds, EditorAction, ObjectChanged};
+
+/// Plugin which handles `ArrangeObjectsEvent` events, which align, distribute or drop stand-alone objects to the
+/// ground.
+///
+/// Objects are arranged using the bounds of their meshes, including any entities in their `ExternalRelations`, which
+/// are moved along with the object. Attachables, `StaticLocked` and `Locked` objects, and objects on a locked layer,
+/// are ignored.
+pub(crate) struct ArrangePlugin;
+
+impl Plugin for ArrangePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ArrangeObjectsEvent>().add_systems(
+            Update,
+            handle_arrange_events.run_if(on_event::<ArrangeObjectsEvent>()),
+        );
+    }
+}
+
+/// A command for arranging several objects relative to each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ArrangeCommand {
+    AlignMinX,
+    AlignCenterX,
+    AlignMaxX,
+    AlignMinZ,
+    AlignCenterZ,
+    AlignMaxZ,
+    /// Space the objects evenly along the X axis, between the two outer-most objects
+    DistributeX,
+    /// Space the objects evenly along the Z axis, between the two outer-most objects
+    DistributeZ,
+    /// Move the objects down or up until they rest on the ground
+    DropToGround,
+}
+
+impl ArrangeCommand {
+    pub const ALL: [ArrangeCommand; 9] = [
+        ArrangeCommand::AlignMinX,
+        ArrangeCommand::AlignCenterX,
+        ArrangeCommand::AlignMaxX,
+        ArrangeCommand::AlignMinZ,
+        ArrangeCommand::AlignCenterZ,
+        ArrangeCommand::AlignMaxZ,
+        ArrangeCommand::DistributeX,
+        ArrangeCommand::DistributeZ,
+        ArrangeCommand::DropToGround,
+    ];
+
+    /// Returns the action that triggers this command in the `InputMap`.
+    pub fn action(&self) -> EditorAction {
+        EditorAction::Arrange(*self)
+    }
+
+    /// Returns a label for displaying the command, e.g. in buttons.
+    pub fn label(&self) -> &'static str {
+        match self {
+            ArrangeCommand::AlignMinX => "Align Left",
+            ArrangeCommand::AlignCenterX => "Align Center X",
+            ArrangeCommand::AlignMaxX => "Align Right",
+            ArrangeCommand::AlignMinZ => "Align Back",
+            ArrangeCommand::AlignCenterZ => "Align Center Z",
+            ArrangeCommand::AlignMaxZ => "Align Front",
+            ArrangeCommand::DistributeX => "Distribute X",
+            ArrangeCommand::DistributeZ => "Distribute Z",
+            ArrangeCommand::DropToGround => "Drop to Ground",
+        }
+    }
+
+    /// Returns the offset along `axis` to move each object by, given the `(min, max)` extents of each object along
+    /// `axis`. Returns `None` if the command does not move objects along `axis`.
+    fn deltas(&self, axis: usize, extents: &[(f32, f32)]) -> Option<Vec<f32>> {
+        let command_axis = match self {
+            ArrangeCommand::AlignMinX
+            | ArrangeCommand::AlignCenterX
+            | ArrangeCommand::AlignMaxX
+            | ArrangeCommand::DistributeX => 0,
+            ArrangeCommand::DropToGround => 1,
+            ArrangeCommand::AlignMinZ
+            | ArrangeCommand::AlignCenterZ
+            | ArrangeCommand::AlignMaxZ
+            | ArrangeCommand::DistributeZ => 2,
+        };
+        if axis != command_axis {
+            return None;
+        }
+
+        let center = |(min, max): (f32, f32)| (min + max) / 2.;
+        let deltas = match self {
+            ArrangeCommand::AlignMinX | ArrangeCommand::AlignMinZ => {
+                let target = extents.iter().map(|e| e.0).fold(f32::MAX, f32::min);
+                extents.iter().map(|e| target - e.0).collect()
+            }
+            ArrangeCommand::AlignMaxX | ArrangeCommand::AlignMaxZ => {
+                let target = extents.iter().map(|e| e.1).fold(f32::MIN, f32::max);
+                extents.iter().map(|e| target - e.1).collect()
+            }
+            ArrangeCommand::AlignCenterX | ArrangeCommand::AlignCenterZ => {
+                // Center the objects on the middle of the whole selection
+                let min = extents.iter().map(|e| e.0).fold(f32::MAX, f32::min);
+                let max = extents.iter().map(|e| e.1).fold(f32::MIN, f32::max);
+                let target = center((min, max));
+                extents.iter().map(|e| target - center(*e)).collect()
+            }
+            ArrangeCommand::DistributeX | ArrangeCommand::DistributeZ => {
+                // Keep the outer-most objects in place, and space the centers of the others evenly between them, in
+                // their current order
+                let mut order: Vec<usize> = (0..extents.len()).collect();
+                order.sort_by(|a, b| center(extents[*a]).total_cmp(&center(extents[*b])));
+                let mut deltas = vec![0.; extents.len()];
+                if let (Some(first), Some(last)) = (order.first(), order.last()) {
+                    let start = center(extents[*first]);
+                    let step = (center(extents[*last]) - start) / (extents.len() - 1).max(1) as f32;
+                    for (position, index) in order.iter().enumerate() {
+                        deltas[*index] = start + step * position as f32 - center(extents[*index]);
+                    }
+                }
+                deltas
+            }
+            ArrangeCommand::DropToGround => extents.iter().map(|e| -e.0).collect(),
+        };
+        Some(deltas)
+    }
+}
+
+/// Event used to arrange a list of objects, usually the selection.
+#[derive(Event, Debug, Clone)]
+pub struct ArrangeObjectsEvent {
+    pub command: ArrangeCommand,
+    pub entities: Vec<Entity>,
+}
+
+/// System that moves objects when receiving `ArrangeObjectsEvent` events, and saves a rollback if anything moved.
+///
+/// NOTE: Bounds are computed from the `GlobalTransform` of the meshes, which is only updated at the end of the frame.
+/// Sending several events in the same frame arranges the objects as they were at the start of the frame.
+#[allow(clippy::too_many_arguments)]
+#[allow(clippy::type_complexity)]
+fn handle_arrange_events(
+    mut events: EventReader<ArrangeObjectsEvent>,
+    object_query: Query<
+        (Entity, &GlobalTransform, Option<&ExternalRelations>),
+        (
+            With<PhysicsBody>,
+            Without<Parent>,
+            Without<StaticLocked>,
+            Without<Locked>,
+        ),
+    >,
+    children_query: Query<&Children>,
+    relations_query: Query<&ExternalRelations>,
+    mesh_query: Query<(&Aabb, &GlobalTransform)>,
+    mut transform_query: Query<&mut Transform>,
+    layers: Layers,
+    mut rollback_writer: EventWriter<RollbackSaveEvent>,
+    mut object_changed_writer: EventWriter<ObjectChanged>,
+) {
+    let mut moved = false;
+    for event in events.read() {
+        // Find the world bounds of each object, falling back to its position if it has no meshes
+        let objects: Vec<_> = object_query
+            .iter_many(&event.entities)
+            .filter(|(entity, ..)| !layers.of_object(*entity).is_some_and(|layer| layer.locked))
+            .map(|(entity, transform, relations)| {
+                let bounds =
+                    compute_object_bounds(entity, &children_query, &relations_query, &mesh_query)
+                        .map(|bounds| (bounds.min, bounds.max))
+                        .unwrap_or((transform.translation(), transform.translation()));
+                (entity, bounds, relations)
+            })
+            .collect();
+        if objects.is_empty() {
+            continue;
+        }
+
+        let mut deltas = vec![Vec3::ZERO; objects.len()];
+        for axis in 0..3 {
+            let extents: Vec<(f32, f32)> = objects
+                .iter()
+                .map(|(_, (min, max), _)| (min[axis], max[axis]))
+                .collect();
+            if let Some(axis_deltas) = event.command.deltas(axis, &extents) {
+                for (delta, axis_delta) in deltas.iter_mut().zip(axis_deltas) {
+                    delta[axis] = axis_delta;
+                }
+            }
+        }
+
+        info!(
+            "[Arrange] ==> {:?} {} objects",
+            event.command,
+            objects.len()
+        );
+        for ((entity, _, relations), delta) in objects.iter().zip(deltas) {
+            if delta.abs_diff_eq(Vec3::ZERO, f32::EPSILON) {
+                continue;
+            }
+            // Move the object and any entities in its relations
+            let entities = std::iter::once(*entity).chain(
+                relations
+                    .iter()
+                    .flat_map(|relations| relations.0.iter().copied()),
+            );
+            for entity in entities {
+                if let Ok(mut transform) = transform_query.get_mut(entity) {
+                    transform.translation += delta;
+                }
+            }
+            object_changed_writer.send(ObjectChanged::Transformed(*entity));
+            moved = true;
+        }
+    }
+
+    // Save a single rollback, so arranging can be undone in one step
+    if moved {
+        rollback_writer.send(RollbackSaveEvent);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_align() {
+        let extents = [(0., 2.), (3., 4.), (-1., 5.)];
+        assert_eq!(
+            ArrangeCommand::AlignMinX.deltas(0, &extents),
+            Some(vec![-1., -4., 0.])
+        );
+        assert_eq!(
+            ArrangeCommand::AlignCenterZ.deltas(2, &extents),
+            Some(vec![1., -1.5, 0.])
+        );
+        assert_eq!(ArrangeCommand::AlignMinX.deltas(2, &extents), None);
+    }
+
+    #[test]
+    fn test_distribute() {
+        // Centers at 0, 1 and 10, in a different order to their positions
+        let extents = [(9., 11.), (-1., 1.), (0.5, 1.5)];
+        assert_eq!(
+            ArrangeCommand::DistributeX.deltas(0, &extents),
+            Some(vec![0., 0., 4.])
+        );
+    }
+}
@@ -0,0 +1,227 @@
+use bevy::{
+    ecs::{archetype::Archetypes, component::Components, entity::Entities, system::SystemParam},
+    prelude::*,
+};
+
+use game_state::prelude::*;
+
+use crate::prelude::{AttachRules, AttachTarget, ToolLibrary};
+
+/// System param for checking whether an attachable may be attached to an object, according to the `AttachRules` of the
+/// tool it is placed with.
+#[derive(SystemParam)]
+pub struct AttachTargets<'w, 's> {
+    entities: &'w Entities,
+    archetypes: &'w Archetypes,
+    components: &'w Components,
+    type_registry: Res<'w, AppTypeRegistry>,
+    tool_library: Res<'w, ToolLibrary>,
+    source_query: Query<'w, 's, &'static PrefabSource>,
+    children_query: Query<'w, 's, &'static Children>,
+    attachable_query: Query<'w, 's, (), With<Attachable>>,
+}
+
+impl AttachTargets<'_, '_> {
+    /// Returns the key of the tool an attachable was placed with, and the attach rules of that tool.
+    ///
+    /// Returns `None` for objects that weren't placed with an attachable prefab tool, which may be attached anywhere.
+    pub fn rules_of(&self, entity: Entity) -> Option<(&str, &AttachRules)> {
+        let source = self.source_query.get(entity).ok()?;
+        let rules = self.tool_library.get_attach_rules(&source.tool)?;
+        Some((&source.tool, rules))
+    }
+
+    /// Returns true if `target` is one of the kinds of objects in `rules.attach_to`.
+    pub fn matches(&self, rules: &AttachRules, target: Entity) -> bool {
+        rules.attach_to.is_empty()
+            || rules
+                .attach_to
+                .iter()
+                .any(|attach_target| match attach_target {
+                    AttachTarget::Component(type_path) => self.has_component(target, type_path),
+                    AttachTarget::Tag(tag) => self
+                        .source_query
+                        .get(target)
+                        .is_ok_and(|source| self.tool_library.get_tags(&source.tool).contains(tag)),
+                })
+    }
+
+    /// Returns the number of attachables placed with the tool `tool` that are attached to `target`, not counting
+    /// `exclude`.
+    pub fn count(&self, tool: &str, target: Entity, exclude: Option<Entity>) -> usize {
+        self.children_query
+            .get(target)
+            .into_iter()
+            .flatten()
+            .filter(|child| Some(**child) != exclude && self.attachable_query.contains(**child))
+            .filter(|child| {
+                self.source_query
+                    .get(**child)
+                    .is_ok_and(|source| source.tool == tool)
+            })
+            .count()
+    }
+
+    /// Returns true if an attachable placed with the tool `tool` may be attached to `target`.
+    ///
+    /// * `exclude` - An attachable that shouldn't count towards `AttachRules::max_per_parent`, e.g. because it is being
+    ///   moved.
+    pub fn can_attach(
+        &self,
+        rules: &AttachRules,
+        tool: Option<&str>,
+        target: Entity,
+        exclude: Option<Entity>,
+    ) -> bool {
+        // NOTE: Attachables placed with tools that aren't in the `ToolLibrary` can't be counted
+        let below_max = match (rules.max_per_parent, tool) {
+            (Some(max), Some(tool)) => self.count(tool, target, exclude) < max,
+            _ => true,
+        };
+        below_max && self.matches(rules, target)
+    }
+
+    /// Returns true if the entity has the component with the given type path.
+    fn has_component(&self, entity: Entity, type_path: &str) -> bool {
+        let type_id = self
+            .type_registry
+            .read()
+            .get_with_type_path(type_path)
+            .map(|registration| registration.type_id());
+        let Some(component_id) = type_id.and_then(|type_id| self.components.get_id(type_id)) else {
+            return false;
+        };
+        self.entities
+            .get(entity)
+            .and_then(|location| self.archetypes.get(location.archetype_id))
+            .is_some_and(|archetype| archetype.contains(component_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::system::SystemState, reflect::TypePath};
+
+    use super::*;
+    use crate::prelude::{PrefabAttachableConfig, PrefabConfig, PrefabToolType, Tool, ToolInfo};
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct TestTree;
+
+    fn prefab_tool(key: &str, tags: Vec<String>, rules: Option<AttachRules>) -> ToolInfo {
+        let tool_type = match rules {
+            Some(rules) => PrefabToolType::Attachable(PrefabAttachableConfig {
+                distance: 0.,
+                forward: Vec3::Z,
+                rules,
+            }),
+            None => PrefabToolType::Object,
+        };
+        ToolInfo {
+            key: key.to_string(),
+            name: key.to_string(),
+            tool: Tool::Prefab(PrefabConfig {
+                name: key.to_string(),
+                key: Some(key.to_string()),
+                scene: Handle::default(),
+                tool_type,
+                initial_scale: 1.,
+                scaling: None,
+                allow_overlap: false,
+                tags,
+            }),
+        }
+    }
+
+    fn setup_world() -> World {
+        let mut world = World::new();
+        let type_registry = AppTypeRegistry::default();
+        type_registry.write().register::<TestTree>();
+        world.insert_resource(type_registry);
+
+        let mut tool_library = ToolLibrary::default();
+        tool_library.register_tool(prefab_tool("tree", vec!["tree".to_string()], None));
+        tool_library.register_tool(prefab_tool("bush", vec!["bush".to_string()], None));
+        tool_library.register_tool(prefab_tool(
+            "apple",
+            Vec::new(),
+            Some(AttachRules::default()),
+        ));
+        world.insert_resource(tool_library);
+        world
+    }
+
+    fn spawn_attachable(world: &mut World, tool: &str, parent: Entity) -> Entity {
+        let tool = tool.to_string();
+        let entity = world.spawn((Attachable, PrefabSource { tool })).id();
+        world.entity_mut(parent).push_children(&[entity]);
+        entity
+    }
+
+    #[test]
+    fn test_matches() {
+        let mut world = setup_world();
+        let tree = world
+            .spawn((
+                TestTree,
+                PrefabSource {
+                    tool: "tree".to_string(),
+                },
+            ))
+            .id();
+        let bush = world
+            .spawn(PrefabSource {
+                tool: "bush".to_string(),
+            })
+            .id();
+
+        let by_tag = AttachRules {
+            attach_to: vec![AttachTarget::Tag("tree".to_string())],
+            ..default()
+        };
+        let by_component = AttachRules {
+            attach_to: vec![AttachTarget::Component(TestTree::type_path().to_string())],
+            ..default()
+        };
+        let unregistered = AttachRules {
+            attach_to: vec![AttachTarget::Component(
+                "tree::components::Missing".to_string(),
+            )],
+            ..default()
+        };
+
+        let mut state: SystemState<AttachTargets> = SystemState::new(&mut world);
+        let targets = state.get(&world);
+        assert!(targets.matches(&by_tag, tree));
+        assert!(!targets.matches(&by_tag, bush));
+        assert!(targets.matches(&by_component, tree));
+        assert!(!targets.matches(&by_component, bush));
+        assert!(!targets.matches(&unregistered, tree));
+        assert!(targets.matches(&AttachRules::default(), bush));
+    }
+
+    #[test]
+    fn test_can_attach_max_per_parent() {
+        let mut world = setup_world();
+        let tree = world.spawn(TestTree).id();
+        let first = spawn_attachable(&mut world, "apple", tree);
+        spawn_attachable(&mut world, "apple", tree);
+        // Attachables placed with other tools don't count towards the maximum
+        spawn_attachable(&mut world, "bush", tree);
+
+        let rules = AttachRules {
+            max_per_parent: Some(2),
+            ..default()
+        };
+
+        let mut state: SystemState<AttachTargets> = SystemState::new(&mut world);
+        let targets = state.get(&world);
+        assert_eq!(targets.count("apple", tree, None), 2);
+        assert_eq!(targets.count("apple", tree, Some(first)), 1);
+        assert!(!targets.can_attach(&rules, Some("apple"), tree, None));
+        assert!(targets.can_attach(&rules, Some("apple"), tree, Some(first)));
+        // Attachables placed with tools that aren't in the library can't be counted
+        assert!(targets.can_attach(&rules, None, tree, None));
+    }
+}
@@ -3,6 +3,8 @@ use bevy::prelude::*;
 use game_state::prelude::*;
 use save::prelude::*;
 
+use crate::prelude::ObjectChanged;
+
 /// Plugin which handles `DespawnEntityAndRelations` events, which despawn an entity and any related entities part of
 /// it's "family" (i.e. related entities not in the bevy hierarchy).
 pub struct DeleteEventPlugin;
@@ -20,19 +22,33 @@ impl Plugin for DeleteEventPlugin {
 #[derive(Event)]
 pub struct DespawnEntityAndRelations(pub Entity);
 
-/// System that reads `DespawnEntityAndRelations` events and deletes the entities.
+/// System that reads `DespawnEntityAndRelations` events and deletes the entities. `Locked` objects, and objects on a
+/// locked layer, are not deleted.
+///
+/// A single rollback is saved if any objects were deleted.
+#[allow(clippy::too_many_arguments)]
 fn handle_delete_events(
     mut commands: Commands,
     mut events: EventReader<DespawnEntityAndRelations>,
     family_child_query: Query<&FamilyChild>,
     relations_query: Query<&ExternalRelations>,
+    locked_query: Query<(), With<Locked>>,
+    layers: Layers,
     mut rollback_writer: EventWriter<RollbackSaveEvent>,
+    mut object_changed_writer: EventWriter<ObjectChanged>,
 ) {
+    let mut deleted = false;
     for event in events.read() {
         // Find the top-most entity if the entity is part of a family
         let parent = family_child_query
             .get(event.0)
             .map_or(event.0, |family_child| family_child.0);
+        if locked_query.contains(parent)
+            || layers.of_object(parent).is_some_and(|layer| layer.locked)
+        {
+            warn!("[Delete] ==> Object is locked and cannot be deleted");
+            continue;
+        }
 
         // Despawn any external relations belonging to the entity
         if let Ok(external_relations) = relations_query.get(parent) {
@@ -46,9 +62,13 @@ fn handle_delete_events(
         // despawn the entity
         if let Some(cmds) = commands.get_entity(parent) {
             cmds.despawn_recursive();
+            object_changed_writer.send(ObjectChanged::Despawned(parent));
+            deleted = true;
         }
+    }
 
-        // Save a rollback
+    // Save a single rollback, so deleting multiple objects at once can be undone in one step
+    if deleted {
         rollback_writer.send(RollbackSaveEvent);
     }
 }
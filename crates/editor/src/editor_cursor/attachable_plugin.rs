@@ -3,13 +3,19 @@ use bevy_mod_raycast::prelude::*;
 
 use game_state::prelude::*;
 
-use crate::prelude::{get_camera_and_cursor_pos, pick_entity_from_raycast};
+use crate::prelude::{
+    get_camera_and_cursor_pos, pick_entity_from_raycast, AttachRules, AttachTargets,
+    CursorPlacement, PickableQuery, RotationSettings, SnapSettings,
+};
 
 use super::{cursor_not_blocked, EditorCursorSet};
 
 /// Plugin which handles cursors that place attachables.
 ///
 /// It is responsible for updating the cursor `Transform` and sending `PlaceAttachableEvent` when the mouse is clicked.
+///
+/// Objects the cursor may not be attached to, according to its `AttachRules`, are marked as invalid with the
+/// `CursorPlacement` of the cursor, and clicking them does not place anything.
 pub(crate) struct AttachableCursorPlugin;
 
 impl Plugin for AttachableCursorPlugin {
@@ -37,20 +43,120 @@ const DEFAULT_PLANE_DISTANCE: f32 = 10.;
 pub struct AttachableCursor {
     pub distance: f32,
     pub forward: Vec3,
+    /// Rotation around `forward` in radians. It is rounded to the increment in `RotationSettings`.
+    pub rotation: f32,
+    /// Which objects the cursor may be attached to
+    pub rules: AttachRules,
+    /// Key of the tool in the `ToolLibrary`, used to count the attachables placed with it, see
+    /// `AttachRules::max_per_parent`
+    pub tool: Option<String>,
 }
 
 /// Internal component for tracking the raycast hit data
 #[derive(Component, Default)]
-struct AttachableCursorTarget(Option<HitData>);
-
-/// Internal type describing the raycast hit data
-#[derive(Debug)]
-struct HitData {
-    entity: Entity,
-    world_pos: Vec3,
-    world_normal: Vec3,
-    local_anchor: Vec3,
-    local_normal: Vec3,
+pub(crate) struct AttachableCursorTarget {
+    pub hit: Option<AttachableHit>,
+    /// Whether the `AttachRules` of the cursor don't allow attaching to the hit entity
+    pub rejected: bool,
+}
+
+/// Raycast hit on an entity that accepts attachables, describing where an attachable should be anchored.
+///
+/// The anchor is offset from the surface along the normal, so the attachable sits on top of the surface instead of
+/// inside it.
+#[derive(Debug, Clone)]
+pub struct AttachableHit {
+    pub entity: Entity,
+    pub world_pos: Vec3,
+    pub world_normal: Vec3,
+    pub local_anchor: Vec3,
+    pub local_normal: Vec3,
+}
+
+impl AttachableHit {
+    /// Creates hit data from a raycast intersection with `entity`.
+    ///
+    /// * `distance` - Distance to offset the anchor from the surface. It is multiplied by `scale`, which should be the
+    ///   world scale of the attachable.
+    pub fn from_intersection(
+        entity: Entity,
+        intersection: &IntersectionData,
+        target_transform: &GlobalTransform,
+        distance: f32,
+        scale: f32,
+    ) -> Self {
+        // Get the world coordinates by offsetting along the normal by the `distance`, making sure to scale the
+        // distance.
+        // NOTE: Do not normalize the hit normal before transforming it to local space -- for some reason that causes
+        // issues
+        let world_normal = intersection.normal();
+        let world_pos = intersection.position() + (world_normal.normalize() * distance * scale);
+
+        // Transform the intersection to local coordinates
+        let inverse_affine = target_transform
+            .compute_transform()
+            .compute_affine()
+            .inverse();
+        let local_anchor = inverse_affine.transform_point3(world_pos);
+        // NOTE: We must use the non-normalized normal here, and normalize it afterwards
+        let local_normal = inverse_affine.transform_vector3(world_normal).normalize();
+
+        Self {
+            entity,
+            world_pos,
+            world_normal,
+            local_anchor,
+            local_normal,
+        }
+    }
+
+    /// Snaps the anchor to the grid on the surface that was hit, aligned with the origin of the target entity.
+    ///
+    /// NOTE: The grid is on the plane tangent to the surface at the hit, so on curved surfaces the snapped anchor may
+    /// be slightly off the surface.
+    pub fn snap_to_grid(
+        &mut self,
+        snap_settings: &SnapSettings,
+        target_transform: &GlobalTransform,
+    ) {
+        self.world_pos = snap_settings.snap_to_surface_grid(
+            self.world_pos,
+            target_transform.translation(),
+            self.world_normal,
+        );
+        self.local_anchor = target_transform
+            .compute_transform()
+            .compute_affine()
+            .inverse()
+            .transform_point3(self.world_pos);
+    }
+
+    /// Returns the world transform of an attachable anchored at this hit, rotated by `spin` radians around `forward`.
+    pub fn world_transform(&self, forward: Vec3, spin: f32, scale: Vec3) -> Transform {
+        Transform::from_translation(self.world_pos)
+            .with_rotation(
+                Quat::from_rotation_arc(forward, self.world_normal.normalize())
+                    * Quat::from_axis_angle(forward, spin),
+            )
+            .with_scale(scale)
+    }
+
+    /// Returns the transform of an attachable anchored at this hit, relative to the target entity. This is the
+    /// transform to use once the attachable is parented to the target.
+    pub fn local_transform(
+        &self,
+        forward: Vec3,
+        spin: f32,
+        scale: Vec3,
+        target_transform: &GlobalTransform,
+    ) -> Transform {
+        Transform::from_translation(self.local_anchor)
+            .with_rotation(
+                Quat::from_rotation_arc(forward, self.local_normal)
+                    * Quat::from_axis_angle(forward, spin),
+            )
+            .with_scale(scale / target_transform.compute_transform().scale)
+    }
 }
 
 /// Event emitted by this plugin when the mouse is clicked on a valid target
@@ -59,6 +165,8 @@ pub struct PlaceAttachableEvent {
     pub tool: Entity,
     pub target: Entity,
     pub transform: Transform,
+    /// Whether the placement is valid, see `CursorPlacement`. Always true for cursors without a `CursorPlacement`.
+    pub valid: bool,
 }
 
 /// System that sets up newly added `AttachableCursor` entities
@@ -82,11 +190,15 @@ fn update_cursor_position(
     )>,
     target_query: Query<&GlobalTransform, With<AcceptsAttachables>>,
     family_child_query: Query<&FamilyChild>,
-    pickable_query: Query<(), With<Pickable>>,
+    pickable_query: PickableQuery,
     camera_query: Query<(&Camera, &GlobalTransform), With<GameCamera>>,
     windows: Query<&Window>,
+    (rotation_settings, snap_settings): (Res<RotationSettings>, Res<SnapSettings>),
+    attach_targets: AttachTargets,
 ) {
     for (cursor, mut cursor_target, mut transform) in query.iter_mut() {
+        let spin = rotation_settings.snap(cursor.rotation);
+
         // Perform the raycast
         let result = pick_entity_from_raycast(
             &cursor_ray,
@@ -95,34 +207,18 @@ fn update_cursor_position(
             &family_child_query,
         );
 
-        // Map the intersection to a `HitData` type by adding local coordinates to the result
+        // Map the intersection to an `AttachableHit` by adding local coordinates to the result
         let hit_data = result.and_then(|(hit_entity, hit_data)| {
-            // Get the world coordinates by offsetting along the normal by the `cursor.distance`, making sure to scale
-            // the distance.
-            // NOTE: Do not normalize the hit normal before transforming it to local space -- for some reason that
-            // causes issues
-            let world_normal = hit_data.normal();
-            let world_pos = hit_data.position()
-                + (world_normal.normalize() * cursor.distance * transform.scale.x);
-
-            // Transform the intersection to local coordinates
-            let local_coords = target_query
-                .get(hit_entity)
-                .map(|transform| {
-                    let inverse_affine = transform.compute_transform().compute_affine().inverse();
-                    let local_anchor = inverse_affine.transform_point3(world_pos);
-                    // NOTE: We must use the non-normalized normal here, and normalize it afterwards
-                    let local_normal = inverse_affine.transform_vector3(world_normal).normalize();
-                    (local_anchor, local_normal)
-                })
-                .ok();
-
-            local_coords.map(|(local_anchor, local_normal)| HitData {
-                entity: hit_entity,
-                world_pos,
-                world_normal,
-                local_anchor,
-                local_normal,
+            target_query.get(hit_entity).ok().map(|target_transform| {
+                let mut hit = AttachableHit::from_intersection(
+                    hit_entity,
+                    &hit_data,
+                    target_transform,
+                    cursor.distance,
+                    transform.scale.x,
+                );
+                hit.snap_to_grid(&snap_settings, target_transform);
+                hit
             })
         });
 
@@ -130,12 +226,7 @@ fn update_cursor_position(
         // If the raycast was successful, use the hit data. If not, project the cursor position to a fixed distance
         // in front of the camera.
         if let Some(hit_data) = hit_data.as_ref() {
-            *transform = Transform::from_translation(hit_data.world_pos)
-                .with_rotation(Quat::from_rotation_arc(
-                    cursor.forward,
-                    hit_data.world_normal.normalize(),
-                ))
-                .with_scale(transform.scale);
+            *transform = hit_data.world_transform(cursor.forward, spin, transform.scale);
         } else {
             // If there are no collisions, then get the cursor world position at the (0.0, 0.0) plane
             let new_transform = get_non_intersecting_transform(
@@ -143,6 +234,7 @@ fn update_cursor_position(
                 &windows,
                 transform.scale,
                 cursor.forward,
+                spin,
             );
             if let Some(new_transform) = new_transform {
                 *transform = new_transform;
@@ -150,19 +242,26 @@ fn update_cursor_position(
         }
 
         // Update cursor target
-        cursor_target.0 = hit_data;
+        cursor_target.rejected = hit_data.as_ref().is_some_and(|hit| {
+            !attach_targets.can_attach(&cursor.rules, cursor.tool.as_deref(), hit.entity, None)
+        });
+        cursor_target.hit = hit_data;
     }
 }
 
 /// Utility that projects the cursor to a point a fixed distance in front of the camera.
 /// This ensures that the cursor transform still follows the mouse. It should appear that the object you're placing is
 /// floating in the air a short distance in front of the camera.
+///
+/// NOTE: Rays of an orthographic camera are parallel and start on its near plane, rather than at the camera, so the
+/// plane is placed in front of the start of the ray instead of the camera.
 #[allow(clippy::too_many_arguments)]
 fn get_non_intersecting_transform(
     camera_query: &Query<(&Camera, &GlobalTransform), With<GameCamera>>,
     windows: &Query<&Window>,
     scale: Vec3,
     forward: Vec3,
+    spin: f32,
 ) -> Option<Transform> {
     let camera_and_cursor_pos = get_camera_and_cursor_pos(windows, camera_query);
 
@@ -171,18 +270,21 @@ fn get_non_intersecting_transform(
         // define the plane at a certain distance in front of the camera. We'll intersect that plane with the cursor
         // to get the resulting transform.
         let camera_forward = camera_transform.forward();
-        let plane_origin = camera_transform.translation() + camera_forward * DEFAULT_PLANE_DISTANCE;
         let plane_normal = -camera_forward;
 
         camera
             .viewport_to_world(camera_transform, cursor_pos)
             .and_then(|ray| {
+                let plane_origin = ray.origin + camera_forward * DEFAULT_PLANE_DISTANCE;
                 ray.intersect_plane(plane_origin, plane_normal)
                     .map(|distance| ray.get_point(distance))
             })
             .map(|translation| {
                 Transform::from_translation(translation)
-                    .with_rotation(Quat::from_rotation_arc(forward, -camera_forward))
+                    .with_rotation(
+                        Quat::from_rotation_arc(forward, -camera_forward)
+                            * Quat::from_axis_angle(forward, spin),
+                    )
                     .with_scale(scale)
             })
     })
@@ -195,18 +297,25 @@ fn handle_mouse_click(
         &AttachableCursor,
         &Transform,
         &AttachableCursorTarget,
+        Option<&CursorPlacement>,
     )>,
     buttons: Res<Input<MouseButton>>,
     mut place_writer: EventWriter<PlaceAttachableEvent>,
     target_query: Query<&GlobalTransform>,
+    rotation_settings: Res<RotationSettings>,
 ) {
     // STYLE: This saves one level of indentation
     if !buttons.just_pressed(MouseButton::Left) {
         return;
     }
 
-    for (entity, cursor, cursor_transform, cursor_target) in query.iter() {
-        let target_transform_and_hit_data = cursor_target.0.as_ref().and_then(|hit_data| {
+    for (entity, cursor, cursor_transform, cursor_target, placement) in query.iter() {
+        // NOTE: Attach rules are always enforced, unlike other invalid placements, see `PlacementSettings`
+        if cursor_target.rejected {
+            warn!("[Attachable Cursor] ==> Can't attach to this object");
+            continue;
+        }
+        let target_transform_and_hit_data = cursor_target.hit.as_ref().and_then(|hit_data| {
             target_query
                 .get(hit_data.entity)
                 .ok()
@@ -217,16 +326,14 @@ fn handle_mouse_click(
             place_writer.send(PlaceAttachableEvent {
                 tool: entity,
                 // Convert transform to local coordinates. Once we place the object it will be parented to target.
-                transform: cursor_transform
-                    .with_translation(hit_data.local_anchor)
-                    .with_rotation(Quat::from_rotation_arc(
-                        cursor.forward,
-                        hit_data.local_normal,
-                    ))
-                    .with_scale(
-                        cursor_transform.scale / target_transform.compute_transform().scale,
-                    ),
+                transform: hit_data.local_transform(
+                    cursor.forward,
+                    rotation_settings.snap(cursor.rotation),
+                    cursor_transform.scale,
+                    target_transform,
+                ),
                 target: hit_data.entity,
+                valid: placement.map(CursorPlacement::is_valid).unwrap_or(true),
             });
         }
     }
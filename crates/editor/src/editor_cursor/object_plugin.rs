@@ -2,7 +2,10 @@ use bevy::prelude::*;
 
 use game_state::prelude::*;
 
-use crate::prelude::get_camera_and_cursor_pos;
+use crate::prelude::{
+    get_camera_and_cursor_pos, BoundsMode, CursorPlacement, PlacementSettings, RotationSettings,
+    SnapSettings,
+};
 
 use super::{cursor_not_blocked, EditorCursorSet};
 
@@ -27,23 +30,34 @@ impl Plugin for ObjectCursorPlugin {
 }
 
 /// A cursor used for placing objects on the ground
-#[derive(Component)]
-pub struct ObjectCursor;
+#[derive(Component, Default)]
+pub struct ObjectCursor {
+    /// Rotation around the Y axis in radians. It is rounded to the increment in `RotationSettings`.
+    pub rotation: f32,
+}
 
 /// Event emitted by this plugin when the mouse is clicked
 #[derive(Event)]
 pub struct PlaceObjectEvent {
     pub tool: Entity,
     pub transform: Transform,
+    /// Whether the placement is valid, see `CursorPlacement`. Always true for cursors without a `CursorPlacement`.
+    pub valid: bool,
 }
 
 /// System that updates the cursor transform
 fn update_cursor_position(
-    mut query: Query<&mut Transform, With<ObjectCursor>>,
+    mut query: Query<(&mut Transform, &ObjectCursor)>,
     camera_query: Query<(&Camera, &GlobalTransform), With<GameCamera>>,
     windows: Query<&Window>,
+    rotation_settings: Res<RotationSettings>,
+    snap_settings: Res<SnapSettings>,
+    garden_bounds: Res<GardenBounds>,
+    placement_settings: Res<PlacementSettings>,
 ) {
-    for mut transform in query.iter_mut() {
+    for (mut transform, cursor) in query.iter_mut() {
+        transform.rotation = Quat::from_rotation_y(rotation_settings.snap(cursor.rotation));
+
         let camera_and_cursor = get_camera_and_cursor_pos(&windows, &camera_query);
         let new_translation =
             camera_and_cursor.and_then(|(camera, camera_transform, cursor_pos)| {
@@ -56,23 +70,28 @@ fn update_cursor_position(
             });
 
         if let Some(new_translation) = new_translation {
-            transform.translation = new_translation;
+            let new_translation = snap_settings.snap_to_grid(new_translation);
+            transform.translation = match placement_settings.bounds_mode {
+                BoundsMode::Clamp => garden_bounds.clamp(new_translation),
+                BoundsMode::Reject => new_translation,
+            };
         }
     }
 }
 
 /// System that emits `PlaceObjectEvent`s when the mouse is clicked
 fn handle_mouse_click(
-    query: Query<(Entity, &Transform), With<ObjectCursor>>,
+    query: Query<(Entity, &Transform, Option<&CursorPlacement>), With<ObjectCursor>>,
     buttons: Res<Input<MouseButton>>,
     mut place_writer: EventWriter<PlaceObjectEvent>,
 ) {
-    for (entity, transform) in query.iter() {
+    for (entity, transform, placement) in query.iter() {
         if buttons.just_pressed(MouseButton::Left) {
             info!("[Object Cursor] ==> Clicked, emitting PlaceObjectEvent");
             place_writer.send(PlaceObjectEvent {
                 tool: entity,
                 transform: *transform,
+                valid: placement.map(CursorPlacement::is_valid).unwrap_or(true),
             });
         }
     }
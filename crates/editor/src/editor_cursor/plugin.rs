@@ -15,6 +15,7 @@ impl Plugin for EditorCursorPlugin {
                 (
                     EditorCursorSet::Status.run_if(in_game),
                     EditorCursorSet::Transform.run_if(in_game),
+                    EditorCursorSet::Validate.run_if(in_game),
                     EditorCursorSet::Click.run_if(in_game),
                 )
                     .chain(),
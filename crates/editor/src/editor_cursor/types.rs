@@ -13,6 +13,8 @@ pub enum EditorCursorSet {
     Status,
     /// Update the transform of cursor entities.
     Transform,
+    /// Validate where cursors would place their object, see `PlacementPlugin`.
+    Validate,
     /// Handle mouse click actions. This is where events to place the object are emitted. Any event handling systems
     /// should run AFTER this set.
     Click,
@@ -0,0 +1,74 @@
+use bevy::{prelude::*, render::primitives::Aabb};
+
+use game_state::prelude::*;
+
+use crate::prelude::{compute_object_bounds, ObjectBounds};
+
+/// Plugin which handles `FrameObjects` events, which move the game camera so that objects fit in view.
+pub(crate) struct FramingPlugin;
+
+impl Plugin for FramingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<FrameObjects>().add_systems(
+            Update,
+            handle_frame_events.run_if(on_event::<FrameObjects>()),
+        );
+    }
+}
+
+/// Smallest radius to frame, so that objects without meshes aren't framed too closely
+const MIN_FRAME_RADIUS: f32 = 0.5;
+
+/// An event used to frame a list of objects, usually the selection.
+#[derive(Event, Debug, Clone)]
+pub struct FrameObjects(pub Vec<Entity>);
+
+/// System that frames the bounds of the families of the objects, by sending a `CameraEvent::Frame` event.
+///
+/// The family of an object is its top-most ancestor, all of its descendants, and any entities in their
+/// `ExternalRelations`.
+fn handle_frame_events(
+    mut events: EventReader<FrameObjects>,
+    parent_query: Query<&Parent>,
+    transform_query: Query<&GlobalTransform>,
+    children_query: Query<&Children>,
+    relations_query: Query<&ExternalRelations>,
+    mesh_query: Query<(&Aabb, &GlobalTransform)>,
+    mut camera_writer: EventWriter<CameraEvent>,
+) {
+    for event in events.read() {
+        // Find the bounds of all objects, falling back to their positions if they have no meshes
+        let bounds = event
+            .0
+            .iter()
+            .map(|entity| {
+                parent_query
+                    .iter_ancestors(*entity)
+                    .last()
+                    .unwrap_or(*entity)
+            })
+            .filter_map(|root| {
+                compute_object_bounds(root, &children_query, &relations_query, &mesh_query).or_else(
+                    || {
+                        transform_query
+                            .get(root)
+                            .ok()
+                            .map(|transform| ObjectBounds {
+                                min: transform.translation(),
+                                max: transform.translation(),
+                            })
+                    },
+                )
+            })
+            .reduce(|a, b| a.union(&b));
+        let Some(bounds) = bounds else {
+            continue;
+        };
+
+        info!("[Framing] ==> framing {} objects", event.0.len());
+        camera_writer.send(CameraEvent::Frame {
+            center: (bounds.min + bounds.max) / 2.,
+            radius: ((bounds.max - bounds.min).length() / 2.).max(MIN_FRAME_RADIUS),
+        });
+    }
+}
@@ -0,0 +1,183 @@
+use bevy::prelude::*;
+
+use game_effects::selected::SelectEvent;
+use game_state::prelude::*;
+use save::prelude::*;
+
+use crate::prelude::ObjectChanged;
+
+/// Plugin which handles `GroupObjects` and `UngroupObjects` events, which combine stand-alone objects into a `Group`
+/// and split them up again.
+///
+/// Grouped objects become children of the group root, together with the entities in their `ExternalRelations`, so the
+/// group is saved, captured, moved and deleted as a single object.
+pub struct GroupEventPlugin;
+
+impl Plugin for GroupEventPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<GroupObjects>()
+            .add_event::<UngroupObjects>()
+            .add_systems(
+                Update,
+                (
+                    handle_group_events.run_if(on_event::<GroupObjects>()),
+                    handle_ungroup_events.run_if(on_event::<UngroupObjects>()),
+                ),
+            );
+    }
+}
+
+/// An event used to combine stand-alone objects into a new group. At least 2 objects are required.
+#[derive(Event)]
+pub struct GroupObjects(pub Vec<Entity>);
+
+/// An event used to split groups back into their objects. Entities that are not groups are ignored.
+#[derive(Event)]
+pub struct UngroupObjects(pub Vec<Entity>);
+
+/// System that reads `GroupObjects` events, and groups the objects and selects the new group.
+#[allow(clippy::too_many_arguments)]
+#[allow(clippy::type_complexity)]
+fn handle_group_events(
+    mut commands: Commands,
+    mut events: EventReader<GroupObjects>,
+    object_query: Query<
+        (Entity, Option<&ExternalRelations>),
+        (
+            With<PhysicsBody>,
+            Without<Parent>,
+            Without<StaticLocked>,
+            Without<Locked>,
+        ),
+    >,
+    transform_query: Query<&Transform>,
+    object_layer_query: Query<&ObjectLayer>,
+    layers: Layers,
+    mut select_writer: EventWriter<SelectEvent>,
+    mut rollback_writer: EventWriter<RollbackSaveEvent>,
+    mut object_changed_writer: EventWriter<ObjectChanged>,
+) {
+    let mut grouped = false;
+    for event in events.read() {
+        let members: Vec<(Entity, Option<&ExternalRelations>)> = object_query
+            .iter_many(&event.0)
+            .filter(|(entity, _)| !layers.of_object(*entity).is_some_and(|layer| layer.locked))
+            .collect();
+        if members.len() < 2 {
+            warn!("[Group] ==> At least 2 objects are required to make a group");
+            continue;
+        }
+
+        // Place the group root in the middle of the objects.
+        // NOTE: Only along the XZ axes, so the group root stays on the ground.
+        let center = members
+            .iter()
+            .filter_map(|(entity, _)| transform_query.get(*entity).ok())
+            .map(|transform| transform.translation)
+            .sum::<Vec3>()
+            / members.len() as f32
+            * Vec3::new(1., 0., 1.);
+        // NOTE: The group is placed on the layer of the first object
+        let layer = object_layer_query
+            .get(members[0].0)
+            .copied()
+            .unwrap_or_default();
+        let group = commands
+            .spawn((
+                Name::new("Group"),
+                Group,
+                layer,
+                GameMarker,
+                PhysicsBody,
+                SpatialBundle::from_transform(Transform::from_translation(center)),
+                Saveable,
+                DespawnOnLoad,
+            ))
+            .id();
+
+        // Parent the objects and their relations to the group, keeping them where they are
+        for (member, relations) in members.iter() {
+            let entities = std::iter::once(*member).chain(
+                relations
+                    .iter()
+                    .flat_map(|relations| relations.0.iter().copied()),
+            );
+            for entity in entities {
+                if let Ok(transform) = transform_query.get(entity) {
+                    let local_transform = Transform {
+                        translation: transform.translation - center,
+                        ..*transform
+                    };
+                    commands
+                        .entity(entity)
+                        .insert(local_transform)
+                        .set_parent(group);
+                }
+            }
+        }
+
+        info!("[Group] ==> grouped {} objects", members.len());
+        object_changed_writer.send(ObjectChanged::Spawned(group));
+        select_writer.send(SelectEvent(vec![group]));
+        grouped = true;
+    }
+
+    // Save a single rollback, so grouping can be undone in one step
+    if grouped {
+        rollback_writer.send(RollbackSaveEvent);
+    }
+}
+
+/// System that reads `UngroupObjects` events, and moves the objects in each group back to the top level and selects
+/// them.
+///
+/// NOTE: Only top-level groups are split. Groups nested inside them become stand-alone groups. The objects keep the
+/// `ObjectLayer` they had before they were grouped. `Locked` groups and groups on a locked layer are not split.
+#[allow(clippy::too_many_arguments)]
+#[allow(clippy::type_complexity)]
+fn handle_ungroup_events(
+    mut commands: Commands,
+    mut events: EventReader<UngroupObjects>,
+    group_query: Query<
+        (Entity, &Transform, &Children),
+        (With<Group>, Without<Parent>, Without<Locked>),
+    >,
+    transform_query: Query<&Transform>,
+    member_query: Query<(), With<Saveable>>,
+    layers: Layers,
+    mut select_writer: EventWriter<SelectEvent>,
+    mut rollback_writer: EventWriter<RollbackSaveEvent>,
+    mut object_changed_writer: EventWriter<ObjectChanged>,
+) {
+    let mut members = Vec::new();
+    for event in events.read() {
+        for (group, group_transform, children) in group_query.iter_many(&event.0) {
+            if layers.of_object(group).is_some_and(|layer| layer.locked) {
+                continue;
+            }
+
+            // Move all children to the top level, including the relations of the objects
+            for child in children.iter() {
+                if let Ok(transform) = transform_query.get(*child) {
+                    commands
+                        .entity(*child)
+                        .insert(group_transform.mul_transform(*transform))
+                        .remove_parent();
+                }
+                if member_query.contains(*child) {
+                    members.push(*child);
+                }
+            }
+
+            info!("[Group] ==> ungrouped {} objects", children.len());
+            commands.entity(group).despawn();
+            object_changed_writer.send(ObjectChanged::Despawned(group));
+        }
+    }
+
+    // Save a single rollback, so ungrouping can be undone in one step
+    if !members.is_empty() {
+        select_writer.send(SelectEvent(members));
+        rollback_writer.send(RollbackSaveEvent);
+    }
+}
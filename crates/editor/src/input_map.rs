@@ -0,0 +1,417 @@
+use bevy::{ecs::system::SystemParam, prelude::*, reflect::TypePath, utils::HashMap};
+use bevy_common_assets::ron::RonAssetPlugin;
+use serde::{Deserialize, Serialize};
+
+use game_state::prelude::*;
+
+use crate::prelude::{ArrangeCommand, ToolLibrary, ToolStack};
+
+/// Plugin which handles the `InputMap`, and switches tools when their hotkeys are pressed.
+pub(crate) struct InputMapPlugin;
+
+impl Plugin for InputMapPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InputMap>()
+            .init_resource::<KeyboardCaptured>()
+            .add_plugins(RonAssetPlugin::<InputMapAsset>::new(&["input.ron"]))
+            .add_systems(Update, handle_tool_hotkeys.run_if(in_game));
+    }
+}
+
+/// An action in the editor that can be bound to keys in the `InputMap`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum EditorAction {
+    Undo,
+    Redo,
+    Save,
+    Load,
+    Copy,
+    Cut,
+    Paste,
+    /// Export the selection as RON text to the system clipboard
+    ExportClipboard,
+    /// Import RON text from the system clipboard into the clipboard
+    ImportClipboard,
+    /// Delete the selected objects
+    Delete,
+    /// Combine the selected objects into a group
+    Group,
+    /// Split the selected groups back into their objects
+    Ungroup,
+    /// Lock or unlock the selected objects
+    ToggleLocked,
+    /// Hide or show the selected objects
+    ToggleHidden,
+    ToggleSnapping,
+    /// Toggle the scatter brush of prefab tools
+    ToggleBrush,
+    /// Toggle placing arrays of copies with prefab tools
+    ToggleArray,
+    /// Switch between lines, grids and circles of copies
+    CycleArrayShape,
+    IncreaseArrayCount,
+    DecreaseArrayCount,
+    /// Add a row to grids of copies
+    IncreaseArrayRows,
+    /// Remove a row from grids of copies
+    DecreaseArrayRows,
+    IncreaseArraySpacing,
+    DecreaseArraySpacing,
+    /// Held down to rotate objects with the mouse wheel, or the rotate keys
+    Rotate,
+    RotateLeft,
+    RotateRight,
+    /// Held down to scale objects with the mouse wheel
+    Scale,
+    /// Held down while clicking to add or remove objects from the selection
+    ToggleSelection,
+    /// Held down to use larger steps when editing values
+    LargeStep,
+    /// Arrange the selected objects
+    Arrange(ArrangeCommand),
+    /// Move the camera so the selected objects fit in view
+    FrameSelected,
+    /// Store the camera viewpoint in a numbered bookmark
+    StoreBookmark(u8),
+    /// Move the camera to the viewpoint of a numbered bookmark
+    RecallBookmark(u8),
+    /// Switch between the perspective view and the top-down plan view
+    TogglePlanView,
+    /// Switch to the tool with this key in the `ToolLibrary`
+    Tool(String),
+}
+
+/// Modifier keys that can be combined with a key in a `KeyBinding`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Modifier {
+    Control,
+    Shift,
+    Alt,
+}
+
+impl Modifier {
+    const ALL: [Modifier; 3] = [Modifier::Control, Modifier::Shift, Modifier::Alt];
+
+    /// Returns the left and right keys for the modifier.
+    fn keys(&self) -> [KeyCode; 2] {
+        match self {
+            Modifier::Control => [KeyCode::ControlLeft, KeyCode::ControlRight],
+            Modifier::Shift => [KeyCode::ShiftLeft, KeyCode::ShiftRight],
+            Modifier::Alt => [KeyCode::AltLeft, KeyCode::AltRight],
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Modifier::Control => "CTRL",
+            Modifier::Shift => "SHIFT",
+            Modifier::Alt => "ALT",
+        }
+    }
+}
+
+/// A key, optionally combined with modifier keys.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeyBinding {
+    pub key: KeyCode,
+    #[serde(default)]
+    pub modifiers: Vec<Modifier>,
+}
+
+impl KeyBinding {
+    pub fn new(key: KeyCode) -> Self {
+        Self {
+            key,
+            modifiers: Vec::new(),
+        }
+    }
+
+    pub fn with(mut self, modifier: Modifier) -> Self {
+        self.modifiers.push(modifier);
+        self
+    }
+
+    /// Returns true if the key was just pressed while holding exactly the modifiers of this binding.
+    ///
+    /// NOTE: Modifiers must match exactly, so that e.g. `CTRL + C` and `CTRL + SHIFT + C` can be bound to different
+    /// actions.
+    pub fn just_pressed(&self, keys: &Input<KeyCode>) -> bool {
+        keys.just_pressed(self.key)
+            && Modifier::ALL.iter().all(|modifier| {
+                // The key of the binding may itself be a modifier key
+                let is_binding_key = modifier.keys().contains(&self.key);
+                is_binding_key
+                    || keys.any_pressed(modifier.keys()) == self.modifiers.contains(modifier)
+            })
+    }
+
+    /// Returns true while the key and all the modifiers of this binding are held down.
+    pub fn pressed(&self, keys: &Input<KeyCode>) -> bool {
+        keys.pressed(self.key)
+            && self
+                .modifiers
+                .iter()
+                .all(|modifier| keys.any_pressed(modifier.keys()))
+    }
+
+    /// Returns a label for displaying the binding, e.g. "CTRL + Z".
+    pub fn label(&self) -> String {
+        self.modifiers
+            .iter()
+            .map(|modifier| modifier.label().to_string())
+            .chain([format!("{:?}", self.key).to_uppercase()])
+            .collect::<Vec<_>>()
+            .join(" + ")
+    }
+}
+
+/// Number keys used for the default camera bookmark bindings
+const BOOKMARK_KEYS: [KeyCode; 9] = [
+    KeyCode::Key1,
+    KeyCode::Key2,
+    KeyCode::Key3,
+    KeyCode::Key4,
+    KeyCode::Key5,
+    KeyCode::Key6,
+    KeyCode::Key7,
+    KeyCode::Key8,
+    KeyCode::Key9,
+];
+
+/// Resource mapping editor actions to key bindings. Each action can be bound to multiple keys.
+///
+/// The default bindings are replaced per action by the bindings in an `InputMapAsset`, see `InputMap::apply_asset`.
+#[derive(Resource, Debug, Clone)]
+pub struct InputMap {
+    bindings: HashMap<EditorAction, Vec<KeyBinding>>,
+}
+
+impl Default for InputMap {
+    fn default() -> Self {
+        use EditorAction::*;
+        use KeyCode as K;
+        use Modifier::*;
+
+        let both =
+            |left: KeyCode, right: KeyCode| vec![KeyBinding::new(left), KeyBinding::new(right)];
+
+        Self {
+            bindings: HashMap::from_iter(
+                [
+                    (Undo, vec![KeyBinding::new(K::Z).with(Control)]),
+                    (
+                        Redo,
+                        vec![
+                            KeyBinding::new(K::Y).with(Control),
+                            KeyBinding::new(K::Z).with(Control).with(Shift),
+                        ],
+                    ),
+                    (Save, vec![KeyBinding::new(K::S).with(Control)]),
+                    (Load, vec![KeyBinding::new(K::L).with(Control)]),
+                    (Copy, vec![KeyBinding::new(K::C).with(Control)]),
+                    (Cut, vec![KeyBinding::new(K::X).with(Control)]),
+                    (Paste, vec![KeyBinding::new(K::V).with(Control)]),
+                    (
+                        ExportClipboard,
+                        vec![KeyBinding::new(K::C).with(Control).with(Shift)],
+                    ),
+                    (
+                        ImportClipboard,
+                        vec![KeyBinding::new(K::V).with(Control).with(Shift)],
+                    ),
+                    (Delete, vec![KeyBinding::new(K::Delete)]),
+                    (Group, vec![KeyBinding::new(K::G).with(Control)]),
+                    (
+                        Ungroup,
+                        vec![KeyBinding::new(K::G).with(Control).with(Shift)],
+                    ),
+                    (
+                        ToggleLocked,
+                        vec![KeyBinding::new(K::L).with(Control).with(Shift)],
+                    ),
+                    (ToggleHidden, vec![KeyBinding::new(K::H).with(Control)]),
+                    (ToggleSnapping, vec![KeyBinding::new(K::G)]),
+                    (ToggleBrush, vec![KeyBinding::new(K::B)]),
+                    (ToggleArray, vec![KeyBinding::new(K::R)]),
+                    (CycleArrayShape, vec![KeyBinding::new(K::T)]),
+                    (IncreaseArrayCount, vec![KeyBinding::new(K::BracketRight)]),
+                    (DecreaseArrayCount, vec![KeyBinding::new(K::BracketLeft)]),
+                    (
+                        IncreaseArrayRows,
+                        vec![KeyBinding::new(K::BracketRight).with(Control)],
+                    ),
+                    (
+                        DecreaseArrayRows,
+                        vec![KeyBinding::new(K::BracketLeft).with(Control)],
+                    ),
+                    (
+                        IncreaseArraySpacing,
+                        vec![KeyBinding::new(K::BracketRight).with(Shift)],
+                    ),
+                    (
+                        DecreaseArraySpacing,
+                        vec![KeyBinding::new(K::BracketLeft).with(Shift)],
+                    ),
+                    (Rotate, both(K::AltLeft, K::AltRight)),
+                    (RotateLeft, vec![KeyBinding::new(K::Q).with(Alt)]),
+                    (RotateRight, vec![KeyBinding::new(K::E).with(Alt)]),
+                    (Scale, both(K::ControlLeft, K::ControlRight)),
+                    (
+                        ToggleSelection,
+                        [
+                            both(K::ShiftLeft, K::ShiftRight),
+                            both(K::ControlLeft, K::ControlRight),
+                        ]
+                        .concat(),
+                    ),
+                    (LargeStep, both(K::ShiftLeft, K::ShiftRight)),
+                    (
+                        Arrange(ArrangeCommand::AlignMinX),
+                        vec![KeyBinding::new(K::Left).with(Alt)],
+                    ),
+                    (
+                        Arrange(ArrangeCommand::AlignCenterX),
+                        vec![KeyBinding::new(K::H).with(Alt)],
+                    ),
+                    (
+                        Arrange(ArrangeCommand::AlignMaxX),
+                        vec![KeyBinding::new(K::Right).with(Alt)],
+                    ),
+                    // NOTE: The camera looks along -Z, so "up" is the back of the garden
+                    (
+                        Arrange(ArrangeCommand::AlignMinZ),
+                        vec![KeyBinding::new(K::Up).with(Alt)],
+                    ),
+                    (
+                        Arrange(ArrangeCommand::AlignCenterZ),
+                        vec![KeyBinding::new(K::V).with(Alt)],
+                    ),
+                    (
+                        Arrange(ArrangeCommand::AlignMaxZ),
+                        vec![KeyBinding::new(K::Down).with(Alt)],
+                    ),
+                    (
+                        Arrange(ArrangeCommand::DistributeX),
+                        vec![KeyBinding::new(K::H).with(Alt).with(Shift)],
+                    ),
+                    (
+                        Arrange(ArrangeCommand::DistributeZ),
+                        vec![KeyBinding::new(K::V).with(Alt).with(Shift)],
+                    ),
+                    (
+                        Arrange(ArrangeCommand::DropToGround),
+                        vec![KeyBinding::new(K::End)],
+                    ),
+                    (FrameSelected, vec![KeyBinding::new(K::F)]),
+                    (TogglePlanView, vec![KeyBinding::new(K::P)]),
+                    (Tool("pointer".to_string()), vec![KeyBinding::new(K::Key1)]),
+                    (
+                        Tool("duplicate".to_string()),
+                        vec![KeyBinding::new(K::Key2)],
+                    ),
+                ]
+                .into_iter()
+                // Bookmarks 1 to 9 are recalled with `CTRL` and the number key, and stored by also holding `SHIFT`
+                .chain(BOOKMARK_KEYS.iter().zip(1..).flat_map(|(key, slot)| {
+                    [
+                        (
+                            StoreBookmark(slot),
+                            vec![KeyBinding::new(*key).with(Control).with(Shift)],
+                        ),
+                        (
+                            RecallBookmark(slot),
+                            vec![KeyBinding::new(*key).with(Control)],
+                        ),
+                    ]
+                })),
+            ),
+        }
+    }
+}
+
+impl InputMap {
+    /// Returns the bindings for an action.
+    pub fn bindings(&self, action: &EditorAction) -> &[KeyBinding] {
+        self.bindings
+            .get(action)
+            .map_or(&[], |bindings| bindings.as_slice())
+    }
+
+    /// Replaces the bindings for an action. An empty list un-binds the action.
+    pub fn set_bindings(&mut self, action: EditorAction, bindings: Vec<KeyBinding>) {
+        self.bindings.insert(action, bindings);
+    }
+
+    /// Replaces the bindings of all actions defined in an asset. Actions that are not in the asset keep their current
+    /// bindings.
+    pub fn apply_asset(&mut self, asset: &InputMapAsset) {
+        for (action, bindings) in asset.bindings.iter() {
+            self.set_bindings(action.clone(), bindings.clone());
+        }
+    }
+
+    /// Returns true if any binding for the action was just pressed.
+    pub fn just_pressed(&self, action: &EditorAction, keys: &Input<KeyCode>) -> bool {
+        self.bindings(action)
+            .iter()
+            .any(|binding| binding.just_pressed(keys))
+    }
+
+    /// Returns true while any binding for the action is held down.
+    pub fn pressed(&self, action: &EditorAction, keys: &Input<KeyCode>) -> bool {
+        self.bindings(action)
+            .iter()
+            .any(|binding| binding.pressed(keys))
+    }
+
+    /// Returns a label for the first binding of an action, e.g. "CTRL + Z".
+    pub fn label(&self, action: &EditorAction) -> Option<String> {
+        self.bindings(action).first().map(KeyBinding::label)
+    }
+}
+
+/// Asset containing key bindings, loaded from a RON file. See `InputMap::apply_asset`.
+#[derive(Asset, Debug, Serialize, Deserialize, TypePath)]
+pub struct InputMapAsset {
+    pub bindings: HashMap<EditorAction, Vec<KeyBinding>>,
+}
+
+/// Resource that is set while a UI widget, such as a text field, is reading the keyboard. No editor actions are
+/// triggered while it is set.
+#[derive(Resource, Debug, Default)]
+pub struct KeyboardCaptured(pub bool);
+
+/// System param for reading editor actions using the `InputMap`.
+#[derive(SystemParam)]
+pub struct ActionInput<'w> {
+    keys: Res<'w, Input<KeyCode>>,
+    input_map: Res<'w, InputMap>,
+    captured: Res<'w, KeyboardCaptured>,
+}
+
+impl ActionInput<'_> {
+    /// Returns true if any binding for the action was just pressed.
+    pub fn just_pressed(&self, action: &EditorAction) -> bool {
+        !self.captured.0 && self.input_map.just_pressed(action, &self.keys)
+    }
+
+    /// Returns true while any binding for the action is held down.
+    pub fn pressed(&self, action: &EditorAction) -> bool {
+        !self.captured.0 && self.input_map.pressed(action, &self.keys)
+    }
+}
+
+/// System that switches tools when their hotkey is pressed.
+fn handle_tool_hotkeys(
+    input: ActionInput,
+    tool_library: Res<ToolLibrary>,
+    mut tool_stack: ResMut<ToolStack>,
+) {
+    for tool in tool_library.tools.iter() {
+        if input.just_pressed(&EditorAction::Tool(tool.key.clone())) {
+            info!("[Input Map] ==> switching to tool: {}", tool.key);
+            tool_stack.clear();
+            tool_stack.push(tool.tool.clone());
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use bevy::prelude::*;
+
+use game_state::prelude::*;
+use save::prelude::*;
+
+use crate::prelude::ObjectChanged;
+
+/// Plugin which handles `LayerEvent` events, which create and edit layers and move objects between them.
+pub(crate) struct LayerEventPlugin;
+
+impl Plugin for LayerEventPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<LayerEvent>()
+            .add_systems(Update, handle_layer_events.run_if(on_event::<LayerEvent>()));
+    }
+}
+
+/// Event used to edit layers. Layers are referenced by their id.
+#[derive(Event, Debug, Clone)]
+pub enum LayerEvent {
+    /// Add a new layer after the existing layers, and make it the active layer
+    Create,
+    Rename {
+        layer: u32,
+        name: String,
+    },
+    /// Move a layer up (negative) or down (positive) in the layer list
+    Reorder {
+        layer: u32,
+        offset: i32,
+    },
+    ToggleVisible(u32),
+    ToggleLocked(u32),
+    /// Make a layer the active layer, which new objects are placed on
+    SetActive(u32),
+    /// Move stand-alone objects to a layer
+    AssignObjects {
+        layer: u32,
+        entities: Vec<Entity>,
+    },
+}
+
+/// System that edits layers when receiving `LayerEvent` events, and saves a rollback if anything that is saved
+/// changed.
+#[allow(clippy::type_complexity)]
+fn handle_layer_events(
+    mut commands: Commands,
+    mut events: EventReader<LayerEvent>,
+    mut layer_query: Query<&mut Layer>,
+    object_query: Query<
+        (Entity, Option<&ObjectLayer>),
+        (With<Saveable>, Without<Parent>, Without<StaticLocked>),
+    >,
+    mut active_layer: ResMut<ActiveLayer>,
+    mut rollback_writer: EventWriter<RollbackSaveEvent>,
+    mut object_changed_writer: EventWriter<ObjectChanged>,
+) {
+    let mut changed = false;
+    for event in events.read() {
+        info!("[Layer] ==> {event:?}");
+        match event {
+            LayerEvent::Create => {
+                let id = layer_query
+                    .iter()
+                    .map(|layer| layer.id + 1)
+                    .max()
+                    .unwrap_or(DEFAULT_LAYER);
+                let order = layer_query
+                    .iter()
+                    .map(|layer| layer.order + 1)
+                    .max()
+                    .unwrap_or(0);
+                // NOTE: Spawn with `Saveable` right away, so the layer is included in the rollback
+                commands.spawn((
+                    Layer {
+                        id,
+                        name: format!("Layer {id}"),
+                        order,
+                        visible: true,
+                        locked: false,
+                    },
+                    GameMarker,
+                    Saveable,
+                    DespawnOnLoad,
+                ));
+                active_layer.0 = id;
+                changed = true;
+            }
+            LayerEvent::Rename { layer, name } => {
+                let name = name.trim();
+                if name.is_empty() {
+                    warn!("[Layer] ==> Layer names can't be empty");
+                    continue;
+                }
+                if let Some(mut layer) = layer_query.iter_mut().find(|l| l.id == *layer) {
+                    layer.name = name.to_string();
+                    changed = true;
+                }
+            }
+            LayerEvent::Reorder { layer, offset } => {
+                // Move the layer in the sorted list, then renumber all layers to keep their order unique
+                let mut layers: Vec<Mut<Layer>> = layer_query.iter_mut().collect();
+                layers.sort_by_key(|l| (l.order, l.id));
+                let Some(index) = layers.iter().position(|l| l.id == *layer) else {
+                    continue;
+                };
+                let new_index = (index as i32 + offset).clamp(0, layers.len() as i32 - 1) as usize;
+                if new_index == index {
+                    continue;
+                }
+                let moved = layers.remove(index);
+                layers.insert(new_index, moved);
+                for (order, mut layer) in layers.into_iter().enumerate() {
+                    layer.order = order as u32;
+                }
+                changed = true;
+            }
+            LayerEvent::ToggleVisible(layer) => {
+                if let Some(mut layer) = layer_query.iter_mut().find(|l| l.id == *layer) {
+                    layer.visible = !layer.visible;
+                    changed = true;
+                }
+            }
+            LayerEvent::ToggleLocked(layer) => {
+                if let Some(mut layer) = layer_query.iter_mut().find(|l| l.id == *layer) {
+                    layer.locked = !layer.locked;
+                    changed = true;
+                }
+            }
+            LayerEvent::SetActive(layer) => {
+                // NOTE: The active layer is not saved, so there is no need to save a rollback
+                if layer_query.iter().any(|l| l.id == *layer) {
+                    active_layer.0 = *layer;
+                }
+            }
+            LayerEvent::AssignObjects { layer, entities } => {
+                if !layer_query.iter().any(|l| l.id == *layer) {
+                    continue;
+                }
+                for (entity, object_layer) in object_query.iter_many(entities) {
+                    if object_layer.map_or(DEFAULT_LAYER, |object_layer| object_layer.0) == *layer {
+                        continue;
+                    }
+                    commands.entity(entity).insert(ObjectLayer(*layer));
+                    object_changed_writer.send(ObjectChanged::ComponentEdited {
+                        entity,
+                        component: std::any::type_name::<ObjectLayer>().to_string(),
+                    });
+                    changed = true;
+                }
+            }
+        }
+    }
+
+    // Save a single rollback, so editing layers can be undone in one step
+    if changed {
+        rollback_writer.send(RollbackSaveEvent);
+    }
+}
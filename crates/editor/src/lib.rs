@@ -1,12 +1,28 @@
+mod app;
+mod arrange;
+mod attach_rules;
 mod delete_events;
 mod editor_cursor;
+mod framing;
+mod group_events;
+mod input_map;
+mod layer_events;
+mod object_events;
+mod object_flags;
 mod pick_utils;
+mod placement;
 mod plugin;
+mod rotation;
+mod scaling;
+mod snapping;
 mod tool_stack;
 mod tools;
 
 pub mod prelude {
     pub use crate::{
-        delete_events::*, editor_cursor::*, pick_utils::*, plugin::*, tool_stack::*, tools::*,
+        app::*, arrange::*, attach_rules::*, delete_events::*, editor_cursor::*, framing::*,
+        group_events::*, input_map::*, layer_events::*, object_events::*, object_flags::*,
+        pick_utils::*, placement::*, plugin::*, rotation::*, scaling::*, snapping::*,
+        tool_stack::*, tools::*,
     };
 }
@@ -0,0 +1,36 @@
+use bevy::prelude::*;
+
+/// Event describing a change made to an object in the world by the editor.
+///
+/// Every editor action that creates, removes or modifies an object emits one of these events containing the root
+/// entity of the affected object. This gives systems like autosave, statistics or networking a single stream of world
+/// changes to listen to, instead of each of them listening to tool-specific events.
+///
+/// NOTE: Rollbacks (undo/redo) and level loads replace the entire world and are not reported here. Listen for
+/// `SaveResult` events from the `save` crate to handle those.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub enum ObjectChanged {
+    /// A new object was spawned into the world.
+    Spawned(Entity),
+    /// An object and its relations were de-spawned. The entity will no longer exist when this event is read.
+    Despawned(Entity),
+    /// The `Transform` of an object was changed.
+    Transformed(Entity),
+    /// An attachable was moved to another parent.
+    Reparented { entity: Entity, parent: Entity },
+    /// A component on an object was edited. `component` is the type path of the edited component.
+    ComponentEdited { entity: Entity, component: String },
+}
+
+impl ObjectChanged {
+    /// Returns the root entity of the object that changed.
+    pub fn entity(&self) -> Entity {
+        match self {
+            ObjectChanged::Spawned(entity) => *entity,
+            ObjectChanged::Despawned(entity) => *entity,
+            ObjectChanged::Transformed(entity) => *entity,
+            ObjectChanged::Reparented { entity, .. } => *entity,
+            ObjectChanged::ComponentEdited { entity, .. } => *entity,
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use bevy::prelude::*;
+
+use game_state::prelude::*;
+use save::prelude::*;
+
+use crate::prelude::ObjectChanged;
+
+/// Plugin which handles `ToggleObjectFlag` events, which lock/unlock or hide/show objects.
+pub(crate) struct ObjectFlagPlugin;
+
+impl Plugin for ObjectFlagPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ToggleObjectFlag>().add_systems(
+            Update,
+            handle_toggle_flag_events.run_if(on_event::<ToggleObjectFlag>()),
+        );
+    }
+}
+
+/// A saved flag that can be toggled on objects by the user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObjectFlag {
+    /// See `Locked`
+    Locked,
+    /// See `Hidden`
+    Hidden,
+}
+
+/// Event used to toggle a flag on a list of objects, usually the selection.
+///
+/// If any of the objects are missing the flag, it is added to all of them. Otherwise it is removed from all of them.
+#[derive(Event, Debug, Clone)]
+pub struct ToggleObjectFlag {
+    pub flag: ObjectFlag,
+    pub entities: Vec<Entity>,
+}
+
+/// System that adds or removes flags when receiving `ToggleObjectFlag` events, and saves a rollback if anything
+/// changed. Objects on a locked layer are not changed.
+#[allow(clippy::type_complexity)]
+fn handle_toggle_flag_events(
+    mut commands: Commands,
+    mut events: EventReader<ToggleObjectFlag>,
+    object_query: Query<
+        (Entity, Has<Locked>, Has<Hidden>),
+        (With<Saveable>, Without<StaticLocked>),
+    >,
+    layers: Layers,
+    mut rollback_writer: EventWriter<RollbackSaveEvent>,
+    mut object_changed_writer: EventWriter<ObjectChanged>,
+) {
+    let mut changed = false;
+    for event in events.read() {
+        let objects: Vec<(Entity, bool)> = object_query
+            .iter_many(&event.entities)
+            .filter(|(entity, ..)| !layers.of_object(*entity).is_some_and(|layer| layer.locked))
+            .map(|(entity, locked, hidden)| match event.flag {
+                ObjectFlag::Locked => (entity, locked),
+                ObjectFlag::Hidden => (entity, hidden),
+            })
+            .collect();
+        if objects.is_empty() {
+            continue;
+        }
+
+        let enable = objects.iter().any(|(_, has_flag)| !has_flag);
+        info!(
+            "[Flags] ==> {} {:?} on {} objects",
+            if enable { "setting" } else { "clearing" },
+            event.flag,
+            objects.len()
+        );
+        let component = match event.flag {
+            ObjectFlag::Locked => std::any::type_name::<Locked>(),
+            ObjectFlag::Hidden => std::any::type_name::<Hidden>(),
+        };
+        for (entity, _) in objects {
+            let mut cmds = commands.entity(entity);
+            match (event.flag, enable) {
+                (ObjectFlag::Locked, true) => cmds.insert(Locked),
+                (ObjectFlag::Locked, false) => cmds.remove::<Locked>(),
+                (ObjectFlag::Hidden, true) => cmds.insert(Hidden),
+                (ObjectFlag::Hidden, false) => cmds.remove::<Hidden>(),
+            };
+            object_changed_writer.send(ObjectChanged::ComponentEdited {
+                entity,
+                component: component.to_string(),
+            });
+        }
+        changed = true;
+    }
+
+    // Save a single rollback, so toggling can be undone in one step
+    if changed {
+        rollback_writer.send(RollbackSaveEvent);
+    }
+}
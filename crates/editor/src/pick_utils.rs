@@ -1,33 +1,124 @@
-use bevy::prelude::*;
+use bevy::{ecs::system::SystemParam, prelude::*};
 use bevy_mod_raycast::prelude::*;
 
 use game_state::prelude::*;
 
+/// System param used when picking, for finding `Pickable` entities and objects that can't be picked because they, or
+/// one of their ancestors, are `Locked` or `Hidden`, or because they are on a locked or hidden `Layer`.
+#[derive(SystemParam)]
+#[allow(clippy::type_complexity)]
+pub struct PickableQuery<'w, 's> {
+    query: Query<
+        'w,
+        's,
+        (
+            Has<Pickable>,
+            Has<Locked>,
+            Has<Hidden>,
+            Option<&'static Parent>,
+        ),
+    >,
+    layers: Layers<'w, 's>,
+}
+
+impl PickableQuery<'_, '_> {
+    /// Returns true if the entity has a `Pickable` component.
+    pub fn is_pickable(&self, entity: Entity) -> bool {
+        self.query.get(entity).is_ok_and(|(pickable, ..)| pickable)
+    }
+}
+
+/// Utility that returns true if an object, or any of its ancestors, is `Locked` or `Hidden`, or if the object is on a
+/// locked or hidden layer.
+pub fn is_pick_blocked(entity: Entity, pickable_query: &PickableQuery) -> bool {
+    let mut current = Some(entity);
+    while let Some(entity) = current {
+        let Ok((_, locked, hidden, parent)) = pickable_query.query.get(entity) else {
+            return false;
+        };
+        if locked || hidden {
+            return true;
+        }
+        current = parent.map(|parent| parent.get());
+    }
+    pickable_query
+        .layers
+        .of_object(entity)
+        .is_some_and(|layer| !layer.is_pickable())
+}
+
 /// Utility for picking an entity using a bevy_mod_raycast
+///
+/// `Locked` and `Hidden` objects, and objects on locked or hidden layers, are ignored.
 pub fn pick_entity_from_raycast(
     cursor_ray: &CursorRay,
     raycast: &mut Raycast,
-    pickable_query: &Query<(), With<Pickable>>,
+    pickable_query: &PickableQuery,
     family_child_query: &Query<&FamilyChild>,
 ) -> Option<(Entity, IntersectionData)> {
-    if let Some(cursor_ray) = **cursor_ray {
-        let settings = RaycastSettings {
-            visibility: RaycastVisibility::MustBeVisibleAndInView,
-            filter: &|entity| pickable_query.contains(entity),
-            early_exit_test: &|_| true,
-        };
-        let hits = raycast.cast_ray(cursor_ray, &settings);
-
-        hits.iter().next().map(|(hit_entity, hit_data)| {
-            // get the parent entity from the hit
-            let entity_parent = family_child_query
-                .get(*hit_entity)
-                .map_or(*hit_entity, |family_child| family_child.0);
-            (entity_parent, hit_data.clone())
-        })
-    } else {
-        None
-    }
+    pick_entity_from_raycast_filtered(
+        cursor_ray,
+        raycast,
+        pickable_query,
+        family_child_query,
+        |_| true,
+    )
+}
+
+/// Utility for picking an entity using a bevy_mod_raycast, ignoring any entities for which `filter` returns false, and
+/// any `Locked` or `Hidden` objects or objects on locked or hidden layers.
+///
+/// `filter` is called with the top-most entity of each hit, i.e. after resolving `FamilyChild` relations.
+pub fn pick_entity_from_raycast_filtered(
+    cursor_ray: &CursorRay,
+    raycast: &mut Raycast,
+    pickable_query: &PickableQuery,
+    family_child_query: &Query<&FamilyChild>,
+    filter: impl Fn(Entity) -> bool,
+) -> Option<(Entity, IntersectionData)> {
+    let cursor_ray = (**cursor_ray)?;
+    pick_entity_from_ray(
+        cursor_ray,
+        raycast,
+        pickable_query,
+        family_child_query,
+        filter,
+    )
+}
+
+/// Utility for picking an entity along any ray, ignoring any entities for which `filter` returns false, and any
+/// `Locked` or `Hidden` objects or objects on locked or hidden layers.
+///
+/// `filter` is called with the top-most entity of each hit, i.e. after resolving `FamilyChild` relations.
+pub fn pick_entity_from_ray(
+    ray: Ray3d,
+    raycast: &mut Raycast,
+    pickable_query: &PickableQuery,
+    family_child_query: &Query<&FamilyChild>,
+    filter: impl Fn(Entity) -> bool,
+) -> Option<(Entity, IntersectionData)> {
+    // get the parent entity from the hit
+    let get_parent = |entity: Entity| {
+        family_child_query
+            .get(entity)
+            .map_or(entity, |family_child| family_child.0)
+    };
+
+    let settings = RaycastSettings {
+        visibility: RaycastVisibility::MustBeVisibleAndInView,
+        filter: &|entity| {
+            let parent = get_parent(entity);
+            pickable_query.is_pickable(entity)
+                && !is_pick_blocked(parent, pickable_query)
+                && filter(parent)
+        },
+        early_exit_test: &|_| true,
+    };
+    let hits = raycast.cast_ray(ray, &settings);
+
+    hits.iter()
+        .next()
+        .map(|(hit_entity, hit_data)| (get_parent(*hit_entity), hit_data.clone()))
 }
 
 pub fn get_camera_and_cursor_pos<'a>(
@@ -0,0 +1,334 @@
+use bevy::{math::Affine3A, prelude::*, render::primitives::Aabb};
+
+use game_state::prelude::*;
+use save::prelude::Saveable;
+
+use crate::prelude::{AttachableCursorTarget, EditorCursorSet};
+
+/// Plugin which validates where cursors would place their object.
+///
+/// Cursors with a `CursorPlacement` component are checked each frame for overlaps with existing objects. Objects placed
+/// on the ground are checked against other stand-alone objects, and attachables are checked against the other
+/// attachables on the object they are placed on. The result is sent with `PlaceObjectEvent` and `PlaceAttachableEvent`
+/// events, and it is up to the tool to reject or warn about invalid placements, see `PlacementSettings`.
+///
+/// Objects on the ground are kept inside the `GardenBounds`, and objects left outside when the bounds change are
+/// reported with an `ObjectsOutsideBounds` event.
+pub(crate) struct PlacementPlugin;
+
+impl Plugin for PlacementPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PlacementSettings>()
+            .add_event::<ObjectsOutsideBounds>()
+            .add_systems(
+                Update,
+                (
+                    update_cursor_placement.in_set(EditorCursorSet::Validate),
+                    report_objects_outside_bounds
+                        .run_if(in_game)
+                        .run_if(resource_changed::<GardenBounds>()),
+                ),
+            );
+    }
+}
+
+/// How objects on the ground are kept inside the `GardenBounds` when placing and moving them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BoundsMode {
+    /// Objects are moved to the nearest point inside the bounds
+    #[default]
+    Clamp,
+    /// Placements outside the bounds are invalid, and objects can't be moved outside the bounds
+    Reject,
+}
+
+/// Event emitted when the `GardenBounds` change, containing the stand-alone objects that are now outside the bounds.
+#[derive(Event, Debug, Clone)]
+pub struct ObjectsOutsideBounds(pub Vec<Entity>);
+
+/// Resource controlling how invalid placements are handled.
+#[derive(Resource, Debug, Clone)]
+pub struct PlacementSettings {
+    /// Whether invalid placements are rejected. When false, the object is placed anyway and a warning is logged.
+    pub reject_invalid: bool,
+    /// Amount in world units that objects may overlap before the placement is considered invalid. This stops objects
+    /// that are merely touching from being reported as overlapping.
+    pub tolerance: f32,
+    /// Color of the cursor preview while the placement is invalid
+    pub invalid_color: Color,
+    /// How objects on the ground are kept inside the `GardenBounds`
+    pub bounds_mode: BoundsMode,
+}
+
+impl Default for PlacementSettings {
+    fn default() -> Self {
+        Self {
+            reject_invalid: true,
+            tolerance: 0.05,
+            invalid_color: Color::RED,
+            bounds_mode: BoundsMode::default(),
+        }
+    }
+}
+
+/// Component added to cursors to validate where they would place their object.
+#[derive(Component, Debug, Clone, Default)]
+pub struct CursorPlacement {
+    /// Whether the object may overlap other objects. When true, overlaps are not checked.
+    pub allow_overlap: bool,
+    /// Objects that the cursor currently overlaps
+    pub overlapping: Vec<Entity>,
+    /// Whether the cursor is outside the `GardenBounds`. Only set when using `BoundsMode::Reject`.
+    pub outside_bounds: bool,
+    /// Whether the cursor is over an object it may not be attached to, see `AttachRules`
+    pub rejected_target: bool,
+}
+
+impl CursorPlacement {
+    pub fn new(allow_overlap: bool) -> Self {
+        Self {
+            allow_overlap,
+            overlapping: Vec::new(),
+            outside_bounds: false,
+            rejected_target: false,
+        }
+    }
+
+    /// Returns true if the object can be placed where the cursor currently is.
+    pub fn is_valid(&self) -> bool {
+        !self.outside_bounds
+            && !self.rejected_target
+            && (self.allow_overlap || self.overlapping.is_empty())
+    }
+}
+
+/// Axis-aligned bounding box of an object in world space.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ObjectBounds {
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+impl ObjectBounds {
+    /// Returns the bounds of a mesh `Aabb`, transformed by `transform`.
+    pub fn from_aabb(aabb: &Aabb, transform: &Affine3A) -> Self {
+        let center = Vec3::from(aabb.center);
+        let half_extents = Vec3::from(aabb.half_extents);
+        let (min, max) = [-1., 1.]
+            .into_iter()
+            .flat_map(|x| [-1., 1.].into_iter().map(move |y| (x, y)))
+            .flat_map(|(x, y)| [-1., 1.].into_iter().map(move |z| Vec3::new(x, y, z)))
+            .map(|corner| transform.transform_point3(center + half_extents * corner))
+            .fold((Vec3::MAX, Vec3::MIN), |(min, max), corner| {
+                (min.min(corner), max.max(corner))
+            });
+        Self { min, max }
+    }
+
+    /// Returns bounds containing both bounds.
+    pub fn union(&self, other: &Self) -> Self {
+        Self {
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
+        }
+    }
+
+    /// Returns these bounds transformed by `transform`.
+    pub fn transformed(&self, transform: &Affine3A) -> Self {
+        let aabb = Aabb::from_min_max(self.min, self.max);
+        Self::from_aabb(&aabb, transform)
+    }
+
+    /// Returns true if the bounds overlap by more than `tolerance` along every axis.
+    pub fn overlaps(&self, other: &Self, tolerance: f32) -> bool {
+        let overlap = self.max.min(other.max) - self.min.max(other.min);
+        overlap.cmpgt(Vec3::splat(tolerance)).all()
+    }
+}
+
+/// Utility that returns the world bounds of all meshes in an object, including its descendants and any entities in its
+/// `ExternalRelations`.
+pub fn compute_object_bounds(
+    entity: Entity,
+    children_query: &Query<&Children>,
+    relations_query: &Query<&ExternalRelations>,
+    mesh_query: &Query<(&Aabb, &GlobalTransform)>,
+) -> Option<ObjectBounds> {
+    let mut bounds: Option<ObjectBounds> = None;
+    let mut stack = vec![entity];
+    while let Some(entity) = stack.pop() {
+        if let Ok((aabb, transform)) = mesh_query.get(entity) {
+            let mesh_bounds = ObjectBounds::from_aabb(aabb, &transform.affine());
+            bounds = Some(match bounds {
+                Some(bounds) => bounds.union(&mesh_bounds),
+                None => mesh_bounds,
+            });
+        }
+        if let Ok(children) = children_query.get(entity) {
+            stack.extend(children.iter());
+        }
+        if let Ok(relations) = relations_query.get(entity) {
+            stack.extend(relations.0.iter());
+        }
+    }
+    bounds
+}
+
+/// Utility that returns the bounds of the meshes in a cursor's hierarchy, relative to the cursor.
+///
+/// NOTE: Only the bevy hierarchy is included. Entities in `ExternalRelations` are not parented to the cursor, so they
+/// are not positioned relative to it.
+fn compute_cursor_local_bounds(
+    cursor: Entity,
+    cursor_transform: &GlobalTransform,
+    children_query: &Query<&Children>,
+    mesh_query: &Query<(&Aabb, &GlobalTransform)>,
+) -> Option<ObjectBounds> {
+    let to_local = cursor_transform.affine().inverse();
+    children_query
+        .iter_descendants(cursor)
+        .filter_map(|entity| mesh_query.get(entity).ok())
+        .map(|(aabb, transform)| ObjectBounds::from_aabb(aabb, &(to_local * transform.affine())))
+        .reduce(|a, b| a.union(&b))
+}
+
+/// System that checks cursors for overlaps with existing objects.
+///
+/// NOTE: The `GlobalTransform` of the cursor is only updated at the end of the frame, so the bounds are computed
+/// relative to the cursor and then moved to its current `Transform`.
+#[allow(clippy::type_complexity)]
+#[allow(clippy::too_many_arguments)]
+fn update_cursor_placement(
+    mut cursor_query: Query<(
+        Entity,
+        &Transform,
+        &GlobalTransform,
+        &mut CursorPlacement,
+        Option<&AttachableCursorTarget>,
+    )>,
+    object_query: Query<Entity, (With<Saveable>, Without<Parent>, Without<StaticLocked>)>,
+    attachable_query: Query<(), (With<Saveable>, With<Attachable>)>,
+    children_query: Query<&Children>,
+    relations_query: Query<&ExternalRelations>,
+    mesh_query: Query<(&Aabb, &GlobalTransform)>,
+    settings: Res<PlacementSettings>,
+    garden_bounds: Res<GardenBounds>,
+) {
+    for (entity, transform, global_transform, mut placement, attachable_target) in
+        cursor_query.iter_mut()
+    {
+        // Only objects on the ground are kept inside the bounds, attachables are kept on their target instead
+        let outside_bounds = settings.bounds_mode == BoundsMode::Reject
+            && attachable_target.is_none()
+            && !garden_bounds.contains(transform.translation);
+        if placement.outside_bounds != outside_bounds {
+            placement.outside_bounds = outside_bounds;
+        }
+        let rejected_target = attachable_target.is_some_and(|target| target.rejected);
+        if placement.rejected_target != rejected_target {
+            placement.rejected_target = rejected_target;
+        }
+
+        if placement.allow_overlap {
+            continue;
+        }
+
+        let bounds =
+            compute_cursor_local_bounds(entity, global_transform, &children_query, &mesh_query)
+                .map(|bounds| bounds.transformed(&transform.compute_affine()));
+
+        // Attachables are checked against the other attachables on their target, and objects against other objects
+        let obstacles: Vec<Entity> = match attachable_target {
+            Some(target) => target
+                .hit
+                .as_ref()
+                .and_then(|hit| children_query.get(hit.entity).ok())
+                .map(|children| {
+                    children
+                        .iter()
+                        .copied()
+                        .filter(|child| attachable_query.contains(*child))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            None => object_query.iter().collect(),
+        };
+
+        let overlapping: Vec<Entity> = match bounds {
+            Some(bounds) => obstacles
+                .into_iter()
+                .filter(|obstacle| {
+                    compute_object_bounds(*obstacle, &children_query, &relations_query, &mesh_query)
+                        .is_some_and(|other| bounds.overlaps(&other, settings.tolerance))
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+
+        if placement.overlapping != overlapping {
+            placement.overlapping = overlapping;
+        }
+    }
+}
+
+/// System that reports the stand-alone objects that are outside the `GardenBounds`, e.g. after the ground was resized.
+#[allow(clippy::type_complexity)]
+fn report_objects_outside_bounds(
+    object_query: Query<
+        (Entity, &GlobalTransform, Option<&Name>),
+        (With<Saveable>, Without<Parent>, Without<StaticLocked>),
+    >,
+    garden_bounds: Res<GardenBounds>,
+    mut outside_writer: EventWriter<ObjectsOutsideBounds>,
+) {
+    let outside: Vec<Entity> = object_query
+        .iter()
+        .filter(|(_, transform, _)| !garden_bounds.contains(transform.translation()))
+        .map(|(entity, _, name)| {
+            warn!("[Placement] ==> Object outside the garden bounds: {name:?} ({entity:?})");
+            entity
+        })
+        .collect();
+    if !outside.is_empty() {
+        outside_writer.send(ObjectsOutsideBounds(outside));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unit_bounds(offset: Vec3) -> ObjectBounds {
+        ObjectBounds {
+            min: offset - Vec3::ONE,
+            max: offset + Vec3::ONE,
+        }
+    }
+
+    #[test]
+    fn test_overlaps() {
+        let bounds = unit_bounds(Vec3::ZERO);
+        assert!(bounds.overlaps(&unit_bounds(Vec3::new(1.5, 0., 0.)), 0.05));
+        assert!(!bounds.overlaps(&unit_bounds(Vec3::new(2.5, 0., 0.)), 0.05));
+        // Touching bounds are within the tolerance
+        assert!(!bounds.overlaps(&unit_bounds(Vec3::new(1.98, 0., 0.)), 0.05));
+    }
+
+    #[test]
+    fn test_transformed() {
+        let bounds = unit_bounds(Vec3::ZERO);
+        let transform = Affine3A::from_scale_rotation_translation(
+            Vec3::splat(2.),
+            Quat::from_rotation_y(std::f32::consts::FRAC_PI_4),
+            Vec3::new(0., 1., 0.),
+        );
+        let transformed = bounds.transformed(&transform);
+        let extent = 2. * std::f32::consts::SQRT_2;
+        assert!(transformed
+            .min
+            .abs_diff_eq(Vec3::new(-extent, -1., -extent), 1e-5));
+        assert!(transformed
+            .max
+            .abs_diff_eq(Vec3::new(extent, 3., extent), 1e-5));
+    }
+}
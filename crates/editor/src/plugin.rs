@@ -1,15 +1,31 @@
 use bevy::prelude::*;
 
-use crate::prelude::{DeleteEventPlugin, EditorCursorPlugin, ToolLibrary, ToolStackPlugin};
+use crate::prelude::{
+    ArrangePlugin, DeleteEventPlugin, EditorCursorPlugin, FramingPlugin, GroupEventPlugin,
+    InputMapPlugin, LayerEventPlugin, ObjectChanged, ObjectFlagPlugin, PlacementPlugin,
+    RotationSettings, ScaleSettings, SnappingPlugin, ToolLibrary, ToolStackPlugin,
+};
 
 pub struct EditorPlugin;
 
 impl Plugin for EditorPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<ToolLibrary>().add_plugins((
-            ToolStackPlugin,
-            DeleteEventPlugin,
-            EditorCursorPlugin,
-        ));
+        app.init_resource::<ToolLibrary>()
+            .init_resource::<RotationSettings>()
+            .init_resource::<ScaleSettings>()
+            .add_event::<ObjectChanged>()
+            .add_plugins((
+                ToolStackPlugin,
+                DeleteEventPlugin,
+                GroupEventPlugin,
+                EditorCursorPlugin,
+                SnappingPlugin,
+                InputMapPlugin,
+                PlacementPlugin,
+                ArrangePlugin,
+                ObjectFlagPlugin,
+                LayerEventPlugin,
+                FramingPlugin,
+            ));
     }
 }
@@ -0,0 +1,84 @@
+use bevy::{ecs::system::SystemParam, input::mouse::MouseWheel, prelude::*};
+
+use game_state::prelude::ScrollCaptured;
+
+use crate::prelude::{ActionInput, EditorAction};
+
+/// Resource controlling how objects are rotated in the editor, both while placing them and after they are placed.
+#[derive(Resource, Debug, Clone)]
+pub struct RotationSettings {
+    /// Optional angle increment in radians. When set, rotations are rounded to the nearest multiple of this angle.
+    pub increment: Option<f32>,
+    /// Angle in radians to rotate per line of mouse scroll.
+    pub scroll_speed: f32,
+    /// Angle in radians to rotate each time a rotate key is pressed.
+    pub key_step: f32,
+}
+
+impl Default for RotationSettings {
+    fn default() -> Self {
+        Self {
+            increment: None,
+            scroll_speed: 5_f32.to_radians(),
+            key_step: 15_f32.to_radians(),
+        }
+    }
+}
+
+impl RotationSettings {
+    /// Rounds an angle to the nearest increment, if there is one.
+    pub fn snap(&self, angle: f32) -> f32 {
+        match self.increment {
+            Some(increment) if increment > 0. => (angle / increment).round() * increment,
+            _ => angle,
+        }
+    }
+}
+
+/// System param for reading rotate controls.
+///
+/// Objects are rotated while holding `EditorAction::Rotate` (`Alt` by default) and scrolling the mouse, or pressing
+/// the `EditorAction::RotateLeft` and `EditorAction::RotateRight` keys (`Q` and `E` by default). A rotation "gesture"
+/// lasts for as long as the modifier is held down, which allows tools to save a single rollback for the entire gesture.
+#[derive(SystemParam)]
+pub struct RotateInput<'w, 's> {
+    input: ActionInput<'w>,
+    scroll_events: EventReader<'w, 's, MouseWheel>,
+    settings: Res<'w, RotationSettings>,
+    scroll_captured: ResMut<'w, ScrollCaptured>,
+}
+
+impl RotateInput<'_, '_> {
+    /// Returns true while the rotate modifier is held down.
+    pub fn is_active(&self) -> bool {
+        self.input.pressed(&EditorAction::Rotate)
+    }
+
+    /// Returns the angle in radians to rotate by this frame.
+    ///
+    /// NOTE: This consumes all scroll events, so it should be called every frame even if the result isn't used.
+    pub fn read_angle(&mut self) -> f32 {
+        let scroll: f32 = self.scroll_events.read().map(|event| event.y).sum();
+        if !self.is_active() {
+            return 0.;
+        }
+        // Stop the camera from zooming while rotating
+        if scroll != 0. {
+            self.scroll_captured.0 = true;
+        }
+
+        let mut angle = scroll * self.settings.scroll_speed;
+        if self.input.just_pressed(&EditorAction::RotateLeft) {
+            angle += self.settings.key_step;
+        }
+        if self.input.just_pressed(&EditorAction::RotateRight) {
+            angle -= self.settings.key_step;
+        }
+        angle
+    }
+
+    /// Returns the rotation settings.
+    pub fn settings(&self) -> &RotationSettings {
+        &self.settings
+    }
+}
@@ -0,0 +1,29 @@
+use bevy::prelude::*;
+
+use crate::prelude::ToolScaling;
+
+/// Resource controlling how objects are scaled in the editor, both while placing them and after they are placed.
+#[derive(Resource, Debug, Clone)]
+pub struct ScaleSettings {
+    /// Amount to scale by per line of mouse scroll.
+    pub scroll_speed: f32,
+    /// When true, attachables are scaled together with the object they are attached to. When false, they keep their
+    /// size in the world.
+    pub scale_attachables_with_parent: bool,
+}
+
+impl Default for ScaleSettings {
+    fn default() -> Self {
+        Self {
+            scroll_speed: 0.01,
+            scale_attachables_with_parent: true,
+        }
+    }
+}
+
+impl ToolScaling {
+    /// Clamps a scale between the minimum and maximum values.
+    pub fn clamp(&self, scale: f32) -> f32 {
+        scale.max(self.min).min(self.max)
+    }
+}
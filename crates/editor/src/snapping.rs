@@ -0,0 +1,177 @@
+use bevy::prelude::*;
+
+use game_state::prelude::*;
+
+use crate::prelude::{ActionInput, EditorAction, RotationSettings};
+
+/// Plugin which toggles snapping with `EditorAction::ToggleSnapping`, and keeps the rotation increment in sync with the
+/// snap settings.
+pub(crate) struct SnappingPlugin;
+
+impl Plugin for SnappingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SnapSettings>().add_systems(
+            Update,
+            (
+                handle_toggle_key.run_if(in_game),
+                sync_rotation_increment.run_if(resource_changed::<SnapSettings>()),
+            )
+                .chain(),
+        );
+    }
+}
+
+/// Resource controlling how objects snap to a grid while placing, moving and rotating them.
+///
+/// Objects on the ground snap to a grid on the ground plane. Attachables snap to a grid on the surface they are
+/// attached to, aligned with the origin of the object they are attached to.
+#[derive(Resource, Debug, Clone)]
+pub struct SnapSettings {
+    pub enabled: bool,
+    /// Size of each grid cell in world units
+    pub grid_size: f32,
+    /// Origin of the grid on the ground plane, as X and Z coordinates
+    pub grid_origin: Vec2,
+    /// Angle increment in radians. While snapping is enabled, this is used as the `RotationSettings` increment.
+    pub angle_step: f32,
+}
+
+impl Default for SnapSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            grid_size: 0.5,
+            grid_origin: Vec2::ZERO,
+            angle_step: 15_f32.to_radians(),
+        }
+    }
+}
+
+impl SnapSettings {
+    /// Snaps a point on the ground to the nearest grid point. The Y coordinate is left unchanged.
+    ///
+    /// Returns the point unchanged when snapping is disabled.
+    pub fn snap_to_grid(&self, point: Vec3) -> Vec3 {
+        if !self.is_active() {
+            return point;
+        }
+        let origin = Vec3::new(self.grid_origin.x, 0., self.grid_origin.y);
+        let snapped = origin + ((point - origin) / self.grid_size).round() * self.grid_size;
+        Vec3::new(snapped.x, point.y, snapped.z)
+    }
+
+    /// Snaps a point to the nearest grid point on the plane through `origin` with the given `normal`. The distance from
+    /// the plane is left unchanged.
+    ///
+    /// Returns the point unchanged when snapping is disabled.
+    pub fn snap_to_surface_grid(&self, point: Vec3, origin: Vec3, normal: Vec3) -> Vec3 {
+        if !self.is_active() {
+            return point;
+        }
+        let Some(normal) = normal.try_normalize() else {
+            return point;
+        };
+        let (u, v) = surface_grid_axes(normal);
+        let offset = point - origin;
+        let snap = |value: f32| (value / self.grid_size).round() * self.grid_size;
+        origin + u * snap(offset.dot(u)) + v * snap(offset.dot(v)) + normal * offset.dot(normal)
+    }
+
+    /// Returns true if snapping is enabled and the grid is valid.
+    fn is_active(&self) -> bool {
+        self.enabled && self.grid_size > 0.
+    }
+}
+
+/// Returns two axes spanning the plane with the given `normal`, used as the axes of a surface grid.
+///
+/// NOTE: The axes follow world up projected onto the plane, falling back to world forward when the plane is nearly
+/// horizontal. This keeps the grid from jumping around as the normal changes slightly, e.g. on curved surfaces.
+fn surface_grid_axes(normal: Vec3) -> (Vec3, Vec3) {
+    let v = Vec3::Y
+        .reject_from_normalized(normal)
+        .try_normalize()
+        .filter(|_| normal.dot(Vec3::Y).abs() < 0.99)
+        .unwrap_or_else(|| Vec3::NEG_Z.reject_from_normalized(normal).normalize());
+    (v.cross(normal), v)
+}
+
+/// System that toggles snapping when the toggle key is pressed
+fn handle_toggle_key(input: ActionInput, mut snap_settings: ResMut<SnapSettings>) {
+    if input.just_pressed(&EditorAction::ToggleSnapping) {
+        snap_settings.enabled = !snap_settings.enabled;
+        info!("[Snapping] ==> enabled: {}", snap_settings.enabled);
+    }
+}
+
+/// System that uses the snap angle step as the rotation increment while snapping is enabled.
+///
+/// NOTE: This overrides any increment set directly on `RotationSettings` whenever the snap settings change.
+fn sync_rotation_increment(
+    snap_settings: Res<SnapSettings>,
+    mut rotation_settings: ResMut<RotationSettings>,
+) {
+    rotation_settings.increment = snap_settings.enabled.then_some(snap_settings.angle_step);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enabled_settings() -> SnapSettings {
+        SnapSettings {
+            enabled: true,
+            grid_size: 0.5,
+            grid_origin: Vec2::new(0.1, -0.2),
+            ..default()
+        }
+    }
+
+    #[test]
+    fn test_snap_to_grid() {
+        let settings = enabled_settings();
+        let snapped = settings.snap_to_grid(Vec3::new(1.3, 2.0, 0.9));
+        assert!(snapped.abs_diff_eq(Vec3::new(1.1, 2.0, 0.8), 1e-5));
+    }
+
+    #[test]
+    fn test_snap_to_grid_disabled() {
+        let settings = SnapSettings::default();
+        let point = Vec3::new(1.3, 2.0, 0.9);
+        assert_eq!(settings.snap_to_grid(point), point);
+    }
+
+    #[test]
+    fn test_snap_to_surface_grid_keeps_distance_from_plane() {
+        let settings = enabled_settings();
+        let origin = Vec3::new(1., 1., 1.);
+        let normal = Vec3::new(1., 1., 0.).normalize();
+        let point = origin + normal * 0.3 + Vec3::new(0.2, -0.2, 0.7);
+        let snapped = settings.snap_to_surface_grid(point, origin, normal);
+
+        // The distance from the plane is unchanged
+        let distance = (snapped - origin).dot(normal);
+        assert!((distance - 0.3).abs() < 1e-5);
+
+        // The point lies on the grid
+        let (u, v) = surface_grid_axes(normal);
+        for axis in [u, v] {
+            let cells = (snapped - origin).dot(axis) / settings.grid_size;
+            assert!((cells - cells.round()).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_surface_grid_axes_are_stable() {
+        // Nearby normals on a curved wall give nearby axes
+        let a = surface_grid_axes(Vec3::new(1., 0.1, 0.).normalize());
+        let b = surface_grid_axes(Vec3::new(1., 0.1, 0.01).normalize());
+        assert!(a.0.abs_diff_eq(b.0, 0.02) && a.1.abs_diff_eq(b.1, 0.02));
+
+        // Nearly horizontal surfaces fall back to world forward
+        let (u, v) = surface_grid_axes(Vec3::Y);
+        assert!(v.abs_diff_eq(Vec3::NEG_Z, 1e-5));
+        assert!(u.abs_diff_eq(Vec3::X, 1e-5) || u.abs_diff_eq(Vec3::NEG_X, 1e-5));
+        assert!(u.dot(v).abs() < 1e-5);
+    }
+}
@@ -46,9 +46,11 @@ impl Plugin for ToolStackPlugin {
 }
 
 /// Initialize the tool state when editor state is entered
-fn initialize_tool_stack(mut tool_stack: ResMut<ToolStack>) {
+fn initialize_tool_stack(mut tool_stack: ResMut<ToolStack>, tool_library: Res<ToolLibrary>) {
     tool_stack.clear();
-    tool_stack.push(Tool::Pointer);
+    if let Some(tool) = tool_library.default_tool.clone() {
+        tool_stack.push(tool);
+    }
 }
 
 /// System that handle events used to push a tool onto the tool stack
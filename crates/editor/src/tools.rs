@@ -1,4 +1,5 @@
+mod editor_tool;
 mod tool_library;
 mod types;
 
-pub use self::{tool_library::*, types::*};
+pub use self::{editor_tool::*, tool_library::*, types::*};
@@ -0,0 +1,42 @@
+use bevy::prelude::*;
+
+use super::types::*;
+
+/// A tool that is added to the editor with `AppEditorToolExt::add_editor_tool`.
+///
+/// It is implemented by the tool's state, which is set to `ACTIVE` while the tool is the current tool, and to its
+/// default value otherwise. Tools add their systems to `OnEnter`, `OnExit` and `in_state` of the state to activate
+/// and de-activate themselves.
+pub trait EditorTool: States {
+    /// Unique key of the tool in the `ToolLibrary`. Also used to bind a hotkey to the tool in the `InputMap`.
+    const KEY: &'static str;
+
+    /// Name of the tool shown in the UI
+    const NAME: &'static str;
+
+    /// The state of the tool while it is the current tool
+    const ACTIVE: Self;
+
+    /// Whether this is the tool that is activated when entering the game. Only one tool should be the default tool.
+    const DEFAULT: bool = false;
+
+    /// Returns the `Tool` which activates this tool when pushed onto the `ToolStack`.
+    fn tool() -> Tool {
+        Tool::Custom(Self::KEY.to_string())
+    }
+}
+
+/// System that listens for `ToolChangedEvent` events and activates or de-activates the tool.
+pub(crate) fn handle_editor_tool_changes<T: EditorTool>(
+    mut events: EventReader<ToolChangedEvent>,
+    mut next_state: ResMut<NextState<T>>,
+    state: Res<State<T>>,
+) {
+    for event in events.read() {
+        if event.0 == T::tool() {
+            next_state.set(T::ACTIVE);
+        } else if *state.get() != T::default() {
+            next_state.set(T::default());
+        }
+    }
+}
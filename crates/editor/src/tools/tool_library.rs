@@ -11,29 +11,11 @@ pub struct ToolInfo {
 }
 
 /// Resource containing the library of registered tool
-#[derive(Resource)]
+#[derive(Resource, Default)]
 pub struct ToolLibrary {
     pub tools: Vec<ToolInfo>,
-}
-
-// TODO: Replace this default value and instead make each tool plugin responsible for adding itself to the library.
-impl Default for ToolLibrary {
-    fn default() -> Self {
-        Self {
-            tools: vec![
-                ToolInfo {
-                    key: "pointer".to_string(),
-                    name: "Pointer".to_string(),
-                    tool: Tool::Pointer,
-                },
-                ToolInfo {
-                    key: "duplicate".to_string(),
-                    name: "Duplicate".to_string(),
-                    tool: Tool::Duplicate,
-                },
-            ],
-        }
-    }
+    /// The tool that is activated when entering the game, see `EditorTool::DEFAULT`
+    pub default_tool: Option<Tool>,
 }
 
 impl ToolLibrary {
@@ -46,6 +28,38 @@ impl ToolLibrary {
         false
     }
 
+    /// Returns the tool registered with the given key.
+    pub fn get_tool(&self, key: &str) -> Option<&ToolInfo> {
+        self.tools.iter().find(|tool| tool.key == key)
+    }
+
+    /// Returns the tags of the prefab tool registered with the given key.
+    pub fn get_tags(&self, key: &str) -> &[String] {
+        match self.get_tool(key).map(|tool| &tool.tool) {
+            Some(Tool::Prefab(config)) => &config.tags,
+            _ => &[],
+        }
+    }
+
+    /// Returns the attach rules of the attachable prefab tool registered with the given key.
+    pub fn get_attach_rules(&self, key: &str) -> Option<&AttachRules> {
+        match &self.get_tool(key)?.tool {
+            Tool::Prefab(PrefabConfig {
+                tool_type: PrefabToolType::Attachable(config),
+                ..
+            }) => Some(&config.rules),
+            _ => None,
+        }
+    }
+
+    /// Returns the scaling limits of the prefab tool registered with the given key, if it allows scaling.
+    pub fn get_scaling(&self, key: &str) -> Option<&ToolScaling> {
+        match &self.get_tool(key)?.tool {
+            Tool::Prefab(config) => config.scaling.as_ref(),
+            _ => None,
+        }
+    }
+
     pub fn register_tool(&mut self, tool_info: ToolInfo) {
         if self.contains_tool(&tool_info.key) {
             panic!("Tool already registered: {}", tool_info.key);
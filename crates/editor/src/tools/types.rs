@@ -3,11 +3,11 @@ use bevy::prelude::*;
 /// All available tool types
 #[derive(Default, Debug, Clone, PartialEq)]
 pub enum Tool {
-    #[default]
-    Pointer,
-    Duplicate,
+    /// A tool added with `AppEditorToolExt::add_editor_tool`, identified by its key in the `ToolLibrary`.
+    Custom(String),
     Prefab(PrefabConfig),
     /// This tool should have nothing associated with it. This type is only activated when we exit the game/editor.
+    #[default]
     None,
 }
 
@@ -36,6 +36,10 @@ pub struct PrefabConfig {
     /// Name of the tool. Should be the object "noun".
     pub name: String,
 
+    /// Key of the tool in the `ToolLibrary`, if it was registered there. Objects placed with the tool are marked with a
+    /// `PrefabSource` containing this key, so they can be traced back to the tool config.
+    pub key: Option<String>,
+
     /// Handle to the scene
     pub scene: Handle<DynamicScene>,
 
@@ -48,6 +52,12 @@ pub struct PrefabConfig {
 
     /// Optional min and max scaling values. A value of `None` will disable scaling.
     pub scaling: Option<ToolScaling>,
+
+    /// Whether the object may be placed overlapping other objects, see `CursorPlacement`.
+    pub allow_overlap: bool,
+
+    /// Tags describing the kind of object, which attachables can be restricted to, see `AttachTarget::Tag`.
+    pub tags: Vec<String>,
 }
 
 /// Configuration for attachable tools. Controls how they are oriented when placing.
@@ -58,6 +68,28 @@ pub struct PrefabAttachableConfig {
     /// The forward direction of the object. Orients the object so this vector faces in the direction of the surface
     /// normal that it's placed on.
     pub forward: Vec3,
+    /// Which objects the attachable may be attached to
+    pub rules: AttachRules,
+}
+
+/// Rules restricting which objects an attachable may be attached to, see `AttachTargets`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AttachRules {
+    /// Kinds of objects the attachable may be attached to. When empty, it may be attached to any object that accepts
+    /// attachables.
+    pub attach_to: Vec<AttachTarget>,
+    /// Maximum number of attachables placed with the same tool on a single object. `None` allows any number.
+    pub max_per_parent: Option<usize>,
+}
+
+/// A kind of object that attachables may be attached to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AttachTarget {
+    /// Objects with the component with this type path, e.g. `tree::components::Tree`. The component must be registered
+    /// in the `AppTypeRegistry`.
+    Component(String),
+    /// Objects placed with a prefab tool that has this tag, see `PrefabConfig::tags`
+    Tag(String),
 }
 
 /// The type of prefab tool, controlling how it is placed into the world.
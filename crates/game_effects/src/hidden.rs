@@ -0,0 +1,125 @@
+use bevy::{prelude::*, render::view::VisibilitySystems};
+
+use game_state::prelude::*;
+
+/// Plugin which hides objects with a `Hidden` component, or on a hidden `Layer`, including the entities in their
+/// `ExternalRelations`, and shows them again when they are no longer hidden.
+pub struct HiddenPlugin;
+
+impl Plugin for HiddenPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            // NOTE: Objects reset their visibility when they are set up, e.g. after loading a level. Setup systems
+            // insert their components with commands, which are only applied at the end of `Update`, so this must run
+            // later.
+            PostUpdate,
+            update_object_visibility.before(VisibilitySystems::VisibilityPropagate),
+        );
+    }
+}
+
+/// System that updates the visibility of objects when they are hidden or shown, or moved to another layer, and of all
+/// objects when a layer changes.
+///
+/// NOTE: Also runs when `ExternalRelations` are added, as the relations of a loaded object are spawned after its
+/// `Hidden` component is added.
+#[allow(clippy::too_many_arguments)]
+#[allow(clippy::type_complexity)]
+fn update_object_visibility(
+    object_query: Query<(Entity, Has<Hidden>, Option<&ExternalRelations>), Without<Disabled>>,
+    changed_query: Query<
+        Entity,
+        Or<(
+            Added<Hidden>,
+            Changed<ObjectLayer>,
+            Added<ExternalRelations>,
+        )>,
+    >,
+    root_query: Query<Entity, (With<PhysicsBody>, Without<Parent>)>,
+    changed_layer_query: Query<(), Changed<Layer>>,
+    mut removed_hidden: RemovedComponents<Hidden>,
+    mut removed_layers: RemovedComponents<Layer>,
+    layers: Layers,
+    mut visibility_query: Query<&mut Visibility>,
+) {
+    let mut entities: Vec<Entity> = changed_query.iter().chain(removed_hidden.read()).collect();
+    // NOTE: Read all removal events, so they don't trigger an update in a later frame
+    let layers_removed = removed_layers.read().count() > 0;
+    if layers_removed || !changed_layer_query.is_empty() {
+        entities.extend(root_query.iter());
+    }
+    if entities.is_empty() {
+        return;
+    }
+
+    for (entity, hidden, relations) in object_query.iter_many(&entities) {
+        let layer_hidden = layers.of_object(entity).is_some_and(|layer| !layer.visible);
+        let visibility = if hidden || layer_hidden {
+            Visibility::Hidden
+        } else {
+            Visibility::Inherited
+        };
+
+        let entities = std::iter::once(entity).chain(
+            relations
+                .iter()
+                .flat_map(|relations| relations.0.iter().copied()),
+        );
+        for entity in entities {
+            if let Ok(mut current) = visibility_query.get_mut(entity) {
+                // NOTE: Avoid triggering change detection every time a layer changes
+                current.set_if_neq(visibility);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Inserts a `SpatialBundle` with commands, like the setup systems do for loaded objects.
+    fn setup_objects(mut commands: Commands, query: Query<Entity, Without<Visibility>>) {
+        for entity in query.iter() {
+            commands.entity(entity).insert(SpatialBundle::default());
+        }
+    }
+
+    fn test_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(HiddenPlugin)
+            .add_systems(Update, setup_objects);
+        app
+    }
+
+    #[test]
+    fn test_hidden_object_set_up_later() {
+        let mut app = test_app();
+        let entity = app.world.spawn(Hidden).id();
+
+        app.update();
+
+        assert_eq!(
+            app.world.get::<Visibility>(entity),
+            Some(&Visibility::Hidden)
+        );
+    }
+
+    #[test]
+    fn test_object_on_hidden_layer_set_up_later() {
+        let mut app = test_app();
+        app.world.spawn(Layer {
+            id: 1,
+            visible: false,
+            ..default()
+        });
+        let entity = app.world.spawn(ObjectLayer(1)).id();
+
+        app.update();
+
+        assert_eq!(
+            app.world.get::<Visibility>(entity),
+            Some(&Visibility::Hidden)
+        );
+    }
+}
@@ -1,3 +1,4 @@
+pub mod hidden;
 mod plugin;
 pub mod selected;
 pub mod transparency;
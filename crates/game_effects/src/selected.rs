@@ -15,16 +15,23 @@ impl Plugin for SelectedPlugin {
         app.add_plugins(WireframePlugin)
             .add_systems(Startup, setup_wireframe_config);
 
-        app.add_event::<SelectEvent>().add_systems(
-            Update,
-            (
-                add_wireframe_to_selected_entities,
-                remove_wireframe_from_de_selected_entities
-                    .run_if(any_component_removed::<Selected>()),
-                handle_select_events.run_if(on_event::<SelectEvent>()),
-            )
-                .run_if(in_game),
-        );
+        app.init_resource::<Selection>()
+            .add_event::<SelectEvent>()
+            .add_systems(
+                Update,
+                (
+                    add_wireframe_to_selected_entities,
+                    (
+                        remove_wireframe_from_de_selected_entities,
+                        remove_despawned_entities_from_selection,
+                    )
+                        .run_if(any_component_removed::<Selected>())
+                        .before(handle_select_events),
+                    deselect_objects_on_locked_layers.before(handle_select_events),
+                    handle_select_events.run_if(on_event::<SelectEvent>()),
+                )
+                    .run_if(in_game),
+            );
     }
 }
 
@@ -32,9 +39,35 @@ impl Plugin for SelectedPlugin {
 #[derive(Component)]
 struct Selected;
 
-/// Event used to select an entity and it's family
+/// Event used to replace the current selection with a list of entities and their families.
+///
+/// Send an empty list to clear the selection.
 #[derive(Event)]
-pub struct SelectEvent(pub Option<Entity>);
+pub struct SelectEvent(pub Vec<Entity>);
+
+/// Resource containing the top-most entity of every selected object, in the order they were selected.
+///
+/// It is updated by this plugin when it receives a `SelectEvent`, and entities are removed from it when they are
+/// de-spawned.
+#[derive(Resource, Default, Debug)]
+pub struct Selection(Vec<Entity>);
+
+impl Selection {
+    /// Returns the selected entities.
+    pub fn entities(&self) -> &[Entity] {
+        &self.0
+    }
+
+    /// Returns true if the entity is selected.
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.0.contains(&entity)
+    }
+
+    /// Returns true if nothing is selected.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
 
 /// setup wireframe rendering in editor mode
 #[cfg(not(target_arch = "wasm32"))]
@@ -44,26 +77,44 @@ fn setup_wireframe_config(mut wireframe_config: ResMut<WireframeConfig>) {
     wireframe_config.global = false;
 }
 
-/// System that handles `SelectEvents` by adding `Selected` and `Wireframe` components to all entities in the target's
+/// System that handles `SelectEvents` by adding `Selected` and `Wireframe` components to all entities in the targets'
 /// relations.
+///
+/// Objects on a locked `Layer` are not selected, so they can't be edited through the selection, e.g. after being
+/// clicked in the outliner.
+#[allow(clippy::too_many_arguments)]
 fn handle_select_events(
     mut commands: Commands,
     mut events: EventReader<SelectEvent>,
+    mut selection: ResMut<Selection>,
     family_child_query: Query<&FamilyChild>,
     external_relations_query: Query<&ExternalRelations>,
     selected_query: Query<Entity, With<Selected>>,
+    group_roots: GroupRoots,
+    children_query: Query<&Children>,
+    layers: Layers,
 ) {
     for event in events.read() {
         // Remove selected component from all other entities
         for entity in selected_query.iter() {
             commands.entity(entity).remove::<Selected>();
         }
+        selection.0.clear();
 
-        if let Some(entity) = event.0 {
-            // Find the top-most entity if it's part of a family
+        for &entity in event.0.iter() {
+            // Find the top-most entity if it's part of a family, and the group it belongs to
             let parent = family_child_query
                 .get(entity)
                 .map_or(entity, |family_child| family_child.0);
+            let parent = group_roots.root(parent);
+            if selection.0.contains(&parent) {
+                continue;
+            }
+            if layers.of_object(parent).is_some_and(|layer| layer.locked) {
+                info!("[Selection] ==> Object is on a locked layer and cannot be selected");
+                continue;
+            }
+            selection.0.push(parent);
 
             // Add selected component to top-most entity
             if let Some(mut cmds) = commands.get_entity(parent) {
@@ -78,10 +129,42 @@ fn handle_select_events(
                     }
                 }
             }
+
+            // Add selected component to everything in a group, so all its objects are highlighted
+            if group_roots.is_group(parent) {
+                for entity in children_query.iter_descendants(parent) {
+                    if let Some(mut cmds) = commands.get_entity(entity) {
+                        cmds.insert((Selected, Wireframe));
+                    }
+                }
+            }
         }
     }
 }
 
+/// System that de-selects objects when their layer is locked, or they are moved to a locked layer.
+#[allow(clippy::type_complexity)]
+fn deselect_objects_on_locked_layers(
+    changed_query: Query<(), Or<(Changed<Layer>, Changed<ObjectLayer>)>>,
+    selection: Res<Selection>,
+    layers: Layers,
+    mut select_writer: EventWriter<SelectEvent>,
+) {
+    if changed_query.is_empty() {
+        return;
+    }
+    let is_locked = |entity: Entity| layers.of_object(entity).is_some_and(|layer| layer.locked);
+    if selection.0.iter().any(|&entity| is_locked(entity)) {
+        let selected = selection
+            .0
+            .iter()
+            .copied()
+            .filter(|&entity| !is_locked(entity))
+            .collect();
+        select_writer.send(SelectEvent(selected));
+    }
+}
+
 /// System that adds `Wireframe` components to selected entities
 #[allow(clippy::type_complexity)]
 fn add_wireframe_to_selected_entities(
@@ -131,3 +214,13 @@ fn remove_wireframe_from_de_selected_entities(
         }
     }
 }
+
+/// Removes de-spawned entities from the `Selection` resource, e.g. after they were deleted or a rollback was applied.
+fn remove_despawned_entities_from_selection(
+    mut selection: ResMut<Selection>,
+    query: Query<(), With<Selected>>,
+) {
+    if selection.0.iter().any(|entity| !query.contains(*entity)) {
+        selection.0.retain(|entity| query.contains(*entity));
+    }
+}
@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 
-use bevy_helpers::material::clone_material_with_transparency;
+use bevy_helpers::material::{clone_color_with_transparency, clone_material_with_transparency};
 
 const TRANSPARENT_MATERIAL_ALPHA: f32 = 0.75;
 
@@ -10,7 +10,11 @@ impl Plugin for TransparentMaterialPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
             Update,
-            (update_transparent_material, revert_transparent_material),
+            (
+                update_transparent_material,
+                revert_transparent_material,
+                update_transparent_material_tint.after(update_transparent_material),
+            ),
         );
     }
 }
@@ -21,6 +25,11 @@ pub struct TransparentMaterial;
 #[derive(Component)]
 pub struct TransparentMaterialAlpha(pub f32);
 
+/// Replaces the color of a transparent material, e.g. to highlight an invalid placement. The original color is restored
+/// when this component is removed.
+#[derive(Component, Clone, Copy, PartialEq)]
+pub struct TransparentMaterialTint(pub Color);
+
 #[derive(Component)]
 struct TransparentMaterialConfig {
     initial: Handle<StandardMaterial>,
@@ -97,3 +106,38 @@ fn revert_transparent_material(
             .remove::<TransparentMaterialConfig>();
     }
 }
+
+/// Applies `TransparentMaterialTint` to the transparent material, and restores the original color when it is removed.
+#[allow(clippy::type_complexity)]
+fn update_transparent_material_tint(
+    query: Query<(
+        &TransparentMaterialConfig,
+        Option<&TransparentMaterialTint>,
+        Option<&TransparentMaterialAlpha>,
+    )>,
+    changed_query: Query<
+        Entity,
+        Or<(
+            Changed<TransparentMaterialTint>,
+            Added<TransparentMaterialConfig>,
+        )>,
+    >,
+    mut removed: RemovedComponents<TransparentMaterialTint>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    for entity in changed_query.iter().chain(removed.read()) {
+        let Ok((config, tint, alpha)) = query.get(entity) else {
+            continue;
+        };
+        let alpha = alpha.map_or(TRANSPARENT_MATERIAL_ALPHA, |a| a.0);
+        let Some(initial) = materials.get(config.initial.id()).cloned() else {
+            continue;
+        };
+        if let Some(material) = materials.get_mut(config.disabled.id()) {
+            *material = clone_material_with_transparency(&initial, alpha);
+            if let Some(tint) = tint {
+                material.base_color = clone_color_with_transparency(tint.0, alpha);
+            }
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use bevy::prelude::*;
+
+use save::prelude::*;
+
+use crate::prelude::*;
+
+/// A numbered camera viewpoint, which can be stored and recalled with `CameraEvent` events. Each bookmark is a separate
+/// entity, which is saved with the level.
+#[derive(Component, Clone, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct CameraBookmark {
+    /// Number used to store and recall the bookmark
+    pub slot: u8,
+    pub name: String,
+    pub position: OrbitPosition,
+}
+
+/// System that initializes newly added `CameraBookmark` entities, e.g. after loading a level.
+pub(crate) fn setup_new_bookmarks(
+    mut commands: Commands,
+    query: Query<Entity, Added<CameraBookmark>>,
+) {
+    for entity in query.iter() {
+        info!("[Camera] ==> Setup new bookmark");
+        commands
+            .entity(entity)
+            .insert((GameMarker, Saveable, DespawnOnLoad));
+    }
+}
@@ -4,10 +4,16 @@
 //! crates/modules.
 use bevy::{prelude::*, reflect::Reflect};
 
+use crate::prelude::{CameraBookmark, Layer};
+
 /// Marker component for de-spawning all game entities when exiting the game state
 #[derive(Component, Clone)]
 pub struct GameMarker;
 
+/// Query filter that excludes saveable entities which store level data rather than objects in the garden, such as
+/// `Layer`s and `CameraBookmark`s.
+pub type WithoutLevelData = (Without<Layer>, Without<CameraBookmark>);
+
 /// Marker component for the game camera
 #[derive(Component)]
 pub struct GameCamera;
@@ -58,6 +64,21 @@ pub struct Pickable;
 #[reflect(Component)]
 pub struct StaticLocked;
 
+/// Marker for objects locked by the user. Locked objects, and anything attached to or grouped under them, cannot be
+/// picked, moved, deleted or duplicated until they are unlocked.
+///
+/// Unlike `StaticLocked`, this is saved with the object and can be toggled in the editor.
+#[derive(Component, Clone, Reflect, Default)]
+#[reflect(Component)]
+pub struct Locked;
+
+/// Marker for objects hidden by the user. Hidden objects are not rendered and cannot be picked.
+///
+/// It is saved with the object and can be toggled in the editor.
+#[derive(Component, Clone, Reflect, Default)]
+#[reflect(Component)]
+pub struct Hidden;
+
 /// Marker added to UI nodes that should block the cursor from interacting with the game world underneath.
 ///
 /// It is used by the cursor tools, via the cursor status resource in `editor::editor_cursor`.
@@ -65,6 +86,40 @@ pub struct StaticLocked;
 #[reflect(Component)]
 pub struct PickableBlock;
 
+/// Describes how an attachable is anchored to the surface of its parent.
+///
+/// This is saved with the attachable, so it can be moved across its parent's surface after it has been placed.
+#[derive(Component, Clone, Copy, Debug, Reflect)]
+#[reflect(Component)]
+pub struct AttachableAnchor {
+    /// Distance between the surface and the origin of the attachable
+    pub distance: f32,
+    /// Direction in the attachable's local space that should point away from the surface
+    pub forward: Vec3,
+    /// Rotation around `forward` in radians
+    #[reflect(default)]
+    pub spin: f32,
+}
+
+impl Default for AttachableAnchor {
+    fn default() -> Self {
+        Self {
+            distance: 0.,
+            forward: Vec3::Y,
+            spin: 0.,
+        }
+    }
+}
+
+/// Component added to objects placed with a prefab tool, containing the key of the tool in the `ToolLibrary`.
+///
+/// This allows the tool config to be looked up after the object was placed, e.g. to get its scaling limits.
+#[derive(Component, Clone, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct PrefabSource {
+    pub tool: String,
+}
+
 /// Marker component for an object that allows attachables to be anchored to it
 #[derive(Component, Clone, Reflect, Default)]
 #[reflect(Component)]
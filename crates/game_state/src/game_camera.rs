@@ -1,31 +1,479 @@
-use bevy::prelude::*;
+use bevy::{
+    input::mouse::{MouseMotion, MouseWheel},
+    prelude::*,
+    render::camera::ScalingMode,
+    transform::TransformSystem,
+};
+
+use save::prelude::*;
 
 use crate::prelude::*;
 
 /// Plugin which manages cameras in game.
 ///
-/// It automatically spawns a GameCamera when you enter the game.
+/// It automatically spawns a GameCamera when you enter the game, and controls it with the mouse:
+/// - Drag with the right mouse button to orbit around the focus point
+/// - Drag with the middle mouse button to pan the focus point along the ground
+/// - Scroll to zoom in and out, unless the scroll was used by something else this frame, see `ScrollCaptured`
+///
+/// The focus point is kept inside the `GardenBounds`, and movement is smoothed out over a few frames. The camera can
+/// also be moved with `CameraEvent` events, e.g. to frame objects or recall a `CameraBookmark`.
+///
+/// The camera can be switched to a top-down plan view with an orthographic projection, which makes it easier to lay out
+/// objects precisely. In the plan view, orbiting only rotates around the Y axis, and zooming changes the size of the
+/// visible area.
 ///
 /// While this example does not include it, this is where you would handle the following:
 /// - Set up camera depending on graphics settings (antialiasing, bloom, tone-mapping)
-/// - Handle camera movement (velocity, following players etc.)
-/// - Handle camera boundaries to prevent the camera from leaving the scene.
 pub struct GameCameraPlugin;
 
 impl Plugin for GameCameraPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(PlayState::SetupLevel), setup_camera_on_enter_game);
+        app.init_resource::<OrbitCameraSettings>()
+            .init_resource::<ScrollCaptured>()
+            .add_event::<CameraEvent>()
+            .add_systems(OnEnter(PlayState::SetupLevel), setup_camera_on_enter_game)
+            .add_systems(First, reset_scroll_captured)
+            .add_systems(
+                PostUpdate,
+                (
+                    handle_camera_events.run_if(on_event::<CameraEvent>()),
+                    handle_camera_input,
+                    update_camera_transform,
+                )
+                    .chain()
+                    // NOTE: Runs after `Update`, so systems that use the mouse wheel can capture it first
+                    .before(TransformSystem::TransformPropagate)
+                    .run_if(in_state(PlayState::Active)),
+            );
+    }
+}
+
+/// Resource that should be set by systems that use the mouse wheel for something else, such as scaling objects, so the
+/// camera doesn't zoom at the same time.
+///
+/// It is reset at the start of every frame, so it must be set every frame the mouse wheel is used.
+#[derive(Resource, Debug, Default)]
+pub struct ScrollCaptured(pub bool);
+
+/// Resource containing the settings of the orbit camera.
+#[derive(Resource, Debug, Clone)]
+pub struct OrbitCameraSettings {
+    /// Radians to orbit per pixel moved with the mouse
+    pub orbit_speed: f32,
+    /// Distance to pan per pixel moved with the mouse, multiplied by the distance to the focus point
+    pub pan_speed: f32,
+    /// Fraction of the distance to zoom by per line scrolled
+    pub zoom_speed: f32,
+    pub min_distance: f32,
+    pub max_distance: f32,
+    /// Lowest pitch in radians, above the ground
+    pub min_pitch: f32,
+    /// Highest pitch in radians, below looking straight down
+    pub max_pitch: f32,
+    /// How quickly the camera catches up with the input. Higher is faster.
+    pub damping: f32,
+}
+
+impl Default for OrbitCameraSettings {
+    fn default() -> Self {
+        Self {
+            orbit_speed: 0.005,
+            pan_speed: 0.0015,
+            zoom_speed: 0.1,
+            min_distance: 2.,
+            max_distance: 40.,
+            min_pitch: 0.05,
+            max_pitch: 1.5,
+            damping: 12.,
+        }
+    }
+}
+
+/// Event used to move the game camera.
+#[derive(Event, Debug, Clone)]
+pub enum CameraEvent {
+    /// Look at a sphere from the current direction, zooming so it fits in view
+    Frame { center: Vec3, radius: f32 },
+    /// Store the current viewpoint in a numbered `CameraBookmark`, replacing any bookmark in that slot
+    StoreBookmark(u8),
+    /// Move the camera to the viewpoint of a numbered `CameraBookmark`
+    RecallBookmark(u8),
+    /// Rename the `CameraBookmark` in a slot
+    RenameBookmark { slot: u8, name: String },
+    /// Switch between the perspective view and the top-down orthographic plan view
+    TogglePlanView,
+}
+
+/// Pitch of the camera in the plan view, looking straight down
+const PLAN_VIEW_PITCH: f32 = std::f32::consts::FRAC_PI_2;
+
+/// Distance of the camera from the focus point in the plan view. It only needs to be high enough to be above all
+/// objects, as the distance of an orthographic camera doesn't change the size of objects.
+const PLAN_VIEW_DISTANCE: f32 = 100.;
+
+/// The position of an orbit camera, relative to the point it is looking at.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct OrbitPosition {
+    /// The point the camera looks at and orbits around
+    pub focus: Vec3,
+    /// Rotation around the Y axis in radians. At 0, the camera looks along -Z.
+    pub yaw: f32,
+    /// Angle above the ground in radians
+    pub pitch: f32,
+    /// Distance from the focus point
+    pub distance: f32,
+}
+
+impl Default for OrbitPosition {
+    fn default() -> Self {
+        Self::from_look_at(Vec3::new(0.0, 1.0, 10.0), Vec3::ZERO)
+    }
+}
+
+impl OrbitPosition {
+    /// Returns the orbit position of a camera at `eye` looking at `focus`.
+    pub fn from_look_at(eye: Vec3, focus: Vec3) -> Self {
+        let offset = eye - focus;
+        let distance = offset.length();
+        Self {
+            focus,
+            yaw: offset.x.atan2(offset.z),
+            pitch: (offset.y / distance).asin(),
+            distance,
+        }
+    }
+
+    /// Returns the rotation of the camera.
+    pub fn rotation(&self) -> Quat {
+        Quat::from_euler(EulerRot::YXZ, self.yaw, -self.pitch, 0.)
+    }
+
+    /// Returns the transform of the camera.
+    pub fn transform(&self) -> Transform {
+        let rotation = self.rotation();
+        Transform {
+            translation: self.focus + rotation * Vec3::Z * self.distance,
+            rotation,
+            ..default()
+        }
+    }
+
+    /// Returns this position looking at a sphere from the same direction, at the distance where the sphere fits in a
+    /// vertical field of view of `fov` radians.
+    pub fn framing(&self, center: Vec3, radius: f32, fov: f32) -> Self {
+        Self {
+            focus: center,
+            distance: radius / (fov / 2.).sin(),
+            ..*self
+        }
+    }
+
+    /// Returns the height of the area visible at the focus point, with a vertical field of view of `fov` radians.
+    ///
+    /// NOTE: Used as the height of the orthographic projection in the plan view, so zooming works the same way in
+    /// both views.
+    pub fn visible_height(&self, fov: f32) -> f32 {
+        2. * self.distance * (fov / 2.).tan()
+    }
+
+    /// Returns this position moved towards `target` by a fraction `t` between 0 and 1.
+    pub fn lerp(&self, target: &OrbitPosition, t: f32) -> Self {
+        Self {
+            focus: self.focus.lerp(target.focus, t),
+            yaw: self.yaw + (target.yaw - self.yaw) * t,
+            pitch: self.pitch + (target.pitch - self.pitch) * t,
+            distance: self.distance + (target.distance - self.distance) * t,
+        }
+    }
+}
+
+/// Component that controls the game camera. The camera is smoothly moved from `current` towards `target`.
+#[derive(Component, Debug, Clone)]
+pub struct OrbitCamera {
+    pub current: OrbitPosition,
+    pub target: OrbitPosition,
+    /// Whether the camera is in the top-down orthographic plan view
+    plan_view: bool,
+    /// The pitch to return to when leaving the plan view
+    perspective_pitch: f32,
+}
+
+impl OrbitCamera {
+    pub fn new(position: OrbitPosition) -> Self {
+        Self {
+            current: position,
+            target: position,
+            plan_view: false,
+            perspective_pitch: position.pitch,
+        }
+    }
+
+    pub fn plan_view(&self) -> bool {
+        self.plan_view
+    }
+
+    /// Switches between the perspective view and the plan view, keeping the focus point, yaw and zoom.
+    pub fn toggle_plan_view(&mut self) {
+        self.plan_view = !self.plan_view;
+        if self.plan_view {
+            self.perspective_pitch = self.target.pitch;
+            self.target.pitch = PLAN_VIEW_PITCH;
+        } else {
+            self.target.pitch = self.perspective_pitch;
+        }
+    }
+
+    /// Returns the target position, with the pitch of the perspective view while in the plan view.
+    pub fn viewpoint(&self) -> OrbitPosition {
+        OrbitPosition {
+            pitch: self.perspective_pitch(),
+            ..self.target
+        }
+    }
+
+    /// Moves the camera to a viewpoint. In the plan view, only its pitch is kept for when leaving the plan view.
+    pub fn set_viewpoint(&mut self, position: OrbitPosition) {
+        self.target = position;
+        if self.plan_view {
+            self.perspective_pitch = position.pitch;
+            self.target.pitch = PLAN_VIEW_PITCH;
+        }
+    }
+
+    fn perspective_pitch(&self) -> f32 {
+        if self.plan_view {
+            self.perspective_pitch
+        } else {
+            self.target.pitch
+        }
     }
 }
 
 fn setup_camera_on_enter_game(mut commands: Commands) {
+    let position = OrbitPosition::default();
     commands.spawn((
         GameMarker,
         GameCamera,
+        OrbitCamera::new(position),
         Camera3dBundle {
-            transform: Transform::from_xyz(0.0, 1.0, 10.0)
-                .looking_at(Vec3::new(0.0, 0.0, 0.0), Vec3::Y),
+            transform: position.transform(),
             ..default()
         },
     ));
 }
+
+fn reset_scroll_captured(mut scroll_captured: ResMut<ScrollCaptured>) {
+    scroll_captured.0 = false;
+}
+
+/// System that handles `CameraEvent` events, and saves a rollback when bookmarks are stored or renamed.
+///
+/// NOTE: Bookmarks are saved with the level, so they are part of every rollback. Without a rollback, the next undo
+/// would restore the previous rollback and silently revert the bookmark, and the change wouldn't count as unsaved. Like
+/// any other edit, this clears the redo history.
+fn handle_camera_events(
+    mut commands: Commands,
+    mut events: EventReader<CameraEvent>,
+    mut camera_query: Query<(&mut OrbitCamera, &Projection)>,
+    mut bookmark_query: Query<&mut CameraBookmark>,
+    settings: Res<OrbitCameraSettings>,
+    mut rollback_writer: EventWriter<RollbackSaveEvent>,
+) {
+    for event in events.read() {
+        for (mut camera, projection) in camera_query.iter_mut() {
+            match event {
+                CameraEvent::Frame { center, radius } => {
+                    let fov = match projection {
+                        Projection::Perspective(perspective) => perspective.fov,
+                        // NOTE: The plan view uses the default field of view for its size, see `visible_height`
+                        Projection::Orthographic(_) => PerspectiveProjection::default().fov,
+                    };
+                    let mut target = camera.target.framing(*center, *radius, fov);
+                    target.distance = target
+                        .distance
+                        .clamp(settings.min_distance, settings.max_distance);
+                    camera.target = target;
+                }
+                CameraEvent::StoreBookmark(slot) => {
+                    info!("[Camera] ==> storing bookmark {slot}");
+                    let position = camera.viewpoint();
+                    match bookmark_query.iter_mut().find(|b| b.slot == *slot) {
+                        Some(mut bookmark) => bookmark.position = position,
+                        // NOTE: Spawn with `Saveable` right away, so the bookmark is included in the rollback
+                        None => {
+                            commands.spawn((
+                                CameraBookmark {
+                                    slot: *slot,
+                                    name: format!("Bookmark {slot}"),
+                                    position,
+                                },
+                                GameMarker,
+                                Saveable,
+                                DespawnOnLoad,
+                            ));
+                        }
+                    }
+                    rollback_writer.send(RollbackSaveEvent);
+                }
+                CameraEvent::RenameBookmark { slot, name } => {
+                    let name = name.trim();
+                    if name.is_empty() {
+                        warn!("[Camera] ==> Bookmark names can't be empty");
+                        continue;
+                    }
+                    match bookmark_query.iter_mut().find(|b| b.slot == *slot) {
+                        Some(mut bookmark) if bookmark.name != name => {
+                            info!("[Camera] ==> renaming bookmark {slot} to {name}");
+                            bookmark.name = name.to_string();
+                            rollback_writer.send(RollbackSaveEvent);
+                        }
+                        Some(_) => (),
+                        None => warn!("[Camera] ==> No bookmark stored in slot {slot}"),
+                    }
+                }
+                CameraEvent::RecallBookmark(slot) => {
+                    match bookmark_query.iter().find(|b| b.slot == *slot) {
+                        Some(bookmark) => {
+                            info!("[Camera] ==> recalling {}", bookmark.name);
+                            camera.set_viewpoint(bookmark.position);
+                        }
+                        None => warn!("[Camera] ==> No bookmark stored in slot {slot}"),
+                    }
+                }
+                CameraEvent::TogglePlanView => {
+                    camera.toggle_plan_view();
+                    info!("[Camera] ==> plan view: {}", camera.plan_view);
+                }
+            }
+        }
+    }
+}
+
+/// System that updates the target position of the camera from the mouse.
+#[allow(clippy::too_many_arguments)]
+fn handle_camera_input(
+    mut query: Query<&mut OrbitCamera>,
+    buttons: Res<Input<MouseButton>>,
+    mut motion_events: EventReader<MouseMotion>,
+    mut scroll_events: EventReader<MouseWheel>,
+    scroll_captured: Res<ScrollCaptured>,
+    settings: Res<OrbitCameraSettings>,
+    bounds: Res<GardenBounds>,
+) {
+    let motion: Vec2 = motion_events.read().map(|event| event.delta).sum();
+    // NOTE: Read all scroll events, so they don't zoom in a later frame
+    let scroll: f32 = scroll_events.read().map(|event| event.y).sum();
+    let scroll = if scroll_captured.0 { 0. } else { scroll };
+
+    for mut camera in query.iter_mut() {
+        let plan_view = camera.plan_view;
+        let target = &mut camera.target;
+
+        if buttons.pressed(MouseButton::Right) {
+            target.yaw -= motion.x * settings.orbit_speed;
+            // NOTE: The plan view always looks straight down
+            if !plan_view {
+                target.pitch = (target.pitch + motion.y * settings.orbit_speed)
+                    .clamp(settings.min_pitch, settings.max_pitch);
+            }
+        }
+
+        if buttons.pressed(MouseButton::Middle) {
+            // Move along the ground, relative to the direction the camera is facing
+            let rotation = Quat::from_rotation_y(target.yaw);
+            let delta = rotation * Vec3::new(-motion.x, 0., -motion.y);
+            target.focus += delta * settings.pan_speed * target.distance;
+        }
+
+        if scroll != 0. {
+            target.distance = (target.distance * (1. - scroll * settings.zoom_speed))
+                .clamp(settings.min_distance, settings.max_distance);
+        }
+
+        target.focus = bounds.clamp(target.focus);
+    }
+}
+
+/// System that smoothly moves the camera towards its target position, and switches its projection when entering or
+/// leaving the plan view.
+fn update_camera_transform(
+    mut query: Query<(&mut OrbitCamera, &mut Transform, &mut Projection)>,
+    settings: Res<OrbitCameraSettings>,
+    time: Res<Time>,
+) {
+    // NOTE: Exponential damping, so the smoothing doesn't depend on the frame rate
+    let t = 1. - (-settings.damping * time.delta_seconds()).exp();
+    for (mut camera, mut transform, mut projection) in query.iter_mut() {
+        let is_orthographic = matches!(*projection, Projection::Orthographic(_));
+        if camera.plan_view != is_orthographic {
+            *projection = if camera.plan_view {
+                Projection::Orthographic(OrthographicProjection::default())
+            } else {
+                Projection::Perspective(PerspectiveProjection::default())
+            };
+        } else if camera.current == camera.target {
+            continue;
+        }
+        let mut current = camera.current.lerp(&camera.target, t);
+        // Snap to the target once it is close enough, to stop updating the transform every frame
+        if current.focus.distance(camera.target.focus) < 0.001
+            && (current.yaw - camera.target.yaw).abs() < 0.0001
+            && (current.pitch - camera.target.pitch).abs() < 0.0001
+            && (current.distance - camera.target.distance).abs() < 0.001
+        {
+            current = camera.target;
+        }
+        camera.current = current;
+
+        if let Projection::Orthographic(orthographic) = projection.as_mut() {
+            // Move the camera far enough away to be above all objects, and zoom by changing the visible area instead
+            let fov = PerspectiveProjection::default().fov;
+            orthographic.scaling_mode = ScalingMode::FixedVertical(current.visible_height(fov));
+            *transform = OrbitPosition {
+                distance: PLAN_VIEW_DISTANCE,
+                ..current
+            }
+            .transform();
+        } else {
+            *transform = current.transform();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_framing() {
+        let position = OrbitPosition::default();
+        let center = Vec3::new(2., 1., -3.);
+        let framed = position.framing(center, 1., std::f32::consts::FRAC_PI_2);
+        assert_eq!(framed.focus, center);
+        assert_eq!((framed.yaw, framed.pitch), (position.yaw, position.pitch));
+        assert!((framed.distance - std::f32::consts::SQRT_2).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_toggle_plan_view() {
+        let position = OrbitPosition::default();
+        let mut camera = OrbitCamera::new(position);
+        camera.toggle_plan_view();
+        assert_eq!(camera.target.pitch, PLAN_VIEW_PITCH);
+        assert_eq!(camera.viewpoint(), position);
+        camera.toggle_plan_view();
+        assert_eq!(camera.target, position);
+    }
+
+    #[test]
+    fn test_from_look_at() {
+        let eye = Vec3::new(3., 4., -2.);
+        let focus = Vec3::new(1., 0., 1.);
+        let transform = OrbitPosition::from_look_at(eye, focus).transform();
+        assert!(transform.translation.abs_diff_eq(eye, 1e-5));
+        assert!(transform
+            .forward()
+            .abs_diff_eq((focus - eye).normalize(), 1e-5));
+    }
+}
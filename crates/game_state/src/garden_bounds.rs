@@ -0,0 +1,90 @@
+use bevy::prelude::*;
+
+/// Resource containing the area of the ground that objects can be placed in, as X and Z coordinates.
+///
+/// It is set by the level's background. A value of `None` means the ground is unbounded.
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq)]
+pub struct GardenBounds(pub Option<Rect>);
+
+impl GardenBounds {
+    /// Returns true if the point is inside the bounds, ignoring its height.
+    pub fn contains(&self, point: Vec3) -> bool {
+        match self.0 {
+            Some(rect) => rect.contains(point.xz()),
+            None => true,
+        }
+    }
+
+    /// Returns the point moved to the nearest point inside the bounds. The Y coordinate is left unchanged.
+    pub fn clamp(&self, point: Vec3) -> Vec3 {
+        match self.0 {
+            Some(rect) => {
+                let clamped = point.xz().clamp(rect.min, rect.max);
+                Vec3::new(clamped.x, point.y, clamped.y)
+            }
+            None => point,
+        }
+    }
+
+    /// Returns `delta` limited so that every point in `starts` stays inside the bounds when moved by it. The Y
+    /// coordinate is left unchanged.
+    ///
+    /// Points that start outside the bounds are allowed to stay where they are, or move towards the bounds.
+    pub fn clamp_delta(&self, starts: impl IntoIterator<Item = Vec3>, delta: Vec3) -> Vec3 {
+        let Some(rect) = self.0 else {
+            return delta;
+        };
+        let (min, max) = starts.into_iter().fold(
+            (Vec2::splat(f32::NEG_INFINITY), Vec2::splat(f32::INFINITY)),
+            |(min, max), start| {
+                let start = start.xz();
+                (
+                    min.max((rect.min - start).min(Vec2::ZERO)),
+                    max.min((rect.max - start).max(Vec2::ZERO)),
+                )
+            },
+        );
+        let clamped = delta.xz().max(min).min(max);
+        Vec3::new(clamped.x, delta.y, clamped.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bounds() -> GardenBounds {
+        GardenBounds(Some(Rect::from_center_size(Vec2::ZERO, Vec2::splat(10.))))
+    }
+
+    #[test]
+    fn test_clamp() {
+        let clamped = bounds().clamp(Vec3::new(7., 1., -2.));
+        assert_eq!(clamped, Vec3::new(5., 1., -2.));
+        assert_eq!(
+            GardenBounds(None).clamp(Vec3::splat(100.)),
+            Vec3::splat(100.)
+        );
+    }
+
+    #[test]
+    fn test_clamp_delta_keeps_all_points_inside() {
+        let starts = [Vec3::new(4., 0., 0.), Vec3::new(-4., 0., 3.)];
+        let delta = bounds().clamp_delta(starts, Vec3::new(3., 0., 4.));
+        assert_eq!(delta, Vec3::new(1., 0., 2.));
+    }
+
+    #[test]
+    fn test_clamp_delta_allows_points_outside_to_stay() {
+        let starts = [Vec3::new(8., 0., 0.)];
+        assert_eq!(bounds().clamp_delta(starts, Vec3::ZERO), Vec3::ZERO);
+        assert_eq!(
+            bounds().clamp_delta(starts, Vec3::new(1., 0., 0.)),
+            Vec3::ZERO
+        );
+        assert_eq!(
+            bounds().clamp_delta(starts, Vec3::new(-2., 0., 0.)),
+            Vec3::new(-2., 0., 0.)
+        );
+    }
+}
@@ -0,0 +1,69 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use save::prelude::*;
+
+use crate::prelude::*;
+
+/// Marker component for the root of a group of objects.
+///
+/// The objects in a group are children of the group root, so they are saved, captured to scenes, moved and deleted
+/// with it. Picking and selecting any object in a group resolves to the top-most group, see `GroupRoots`.
+///
+/// Must be spawned with a `Transform` component.
+#[derive(Component, Clone, Reflect, Default)]
+#[reflect(Component)]
+pub struct Group;
+
+/// System param for finding the group an object belongs to.
+#[derive(SystemParam)]
+pub struct GroupRoots<'w, 's> {
+    parent_query: Query<'w, 's, &'static Parent>,
+    group_query: Query<'w, 's, (), With<Group>>,
+}
+
+impl GroupRoots<'_, '_> {
+    /// Returns the top-most group containing the entity, or the entity itself if it is not part of a group.
+    ///
+    /// NOTE: This does not resolve `FamilyChild` relations, so it should be called with the top-most entity of a
+    /// family.
+    pub fn root(&self, entity: Entity) -> Entity {
+        let mut root = entity;
+        let mut current = entity;
+        while let Ok(parent) = self.parent_query.get(current) {
+            current = parent.get();
+            if self.group_query.contains(current) {
+                root = current;
+            }
+        }
+        root
+    }
+
+    /// Returns true if the entity is the root of a group.
+    pub fn is_group(&self, entity: Entity) -> bool {
+        self.group_query.contains(entity)
+    }
+}
+
+/// System that initializes newly added `Group` entities, e.g. after loading a level.
+#[allow(clippy::type_complexity)]
+pub(crate) fn setup_new_groups(
+    mut commands: Commands,
+    query: Query<(Entity, &Transform, Has<Disabled>), Added<Group>>,
+) {
+    for (entity, transform, disabled) in query.iter() {
+        info!("[Group] ==> Setup new group");
+
+        let mut cmds = commands.entity(entity);
+        cmds.insert((
+            Name::new("Group"),
+            GameMarker,
+            PhysicsBody,
+            SpatialBundle::from_transform(*transform),
+        ));
+
+        // If disabled, this entity should not be saved
+        if !disabled {
+            cmds.insert((Saveable, DespawnOnLoad));
+        }
+    }
+}
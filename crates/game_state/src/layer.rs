@@ -0,0 +1,124 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use save::prelude::*;
+
+use crate::prelude::*;
+
+/// Id of the default layer, which exists in every level. Objects without an `ObjectLayer`, or whose layer does not
+/// exist, belong to it.
+pub const DEFAULT_LAYER: u32 = 0;
+
+/// A layer for organising objects in the editor. Each layer is a separate entity, which is saved with the level.
+///
+/// Objects on a hidden layer are not rendered, and objects on a hidden or locked layer cannot be picked. Objects on a
+/// locked layer cannot be selected or edited either. `StaticLocked` objects, such as the background, are not part of
+/// any layer.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component)]
+pub struct Layer {
+    /// Unique id of the layer, referenced by `ObjectLayer`
+    pub id: u32,
+    pub name: String,
+    /// Position of the layer in the layer list, lowest first
+    pub order: u32,
+    pub visible: bool,
+    pub locked: bool,
+}
+
+impl Default for Layer {
+    fn default() -> Self {
+        Self {
+            id: DEFAULT_LAYER,
+            name: "Default".to_string(),
+            order: 0,
+            visible: true,
+            locked: false,
+        }
+    }
+}
+
+impl Layer {
+    /// Returns true if objects on this layer can be picked.
+    pub fn is_pickable(&self) -> bool {
+        self.visible && !self.locked
+    }
+}
+
+/// Component added to stand-alone objects, containing the id of the `Layer` they belong to.
+///
+/// Attachables and grouped objects belong to the layer of their top-most ancestor.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct ObjectLayer(pub u32);
+
+/// Resource containing the id of the layer that new objects are placed on.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ActiveLayer(pub u32);
+
+/// System param for looking up layers and the layer an object belongs to.
+#[derive(SystemParam)]
+pub struct Layers<'w, 's> {
+    layer_query: Query<'w, 's, (Entity, &'static Layer)>,
+    object_query: Query<
+        'w,
+        's,
+        (Option<&'static ObjectLayer>, Option<&'static Parent>),
+        Without<StaticLocked>,
+    >,
+}
+
+impl Layers<'_, '_> {
+    /// Returns the layer with the given id.
+    pub fn get(&self, id: u32) -> Option<(Entity, &Layer)> {
+        self.layer_query.iter().find(|(_, layer)| layer.id == id)
+    }
+
+    /// Returns all layers, sorted by their order.
+    pub fn sorted(&self) -> Vec<(Entity, &Layer)> {
+        let mut layers: Vec<(Entity, &Layer)> = self.layer_query.iter().collect();
+        layers.sort_by_key(|(_, layer)| (layer.order, layer.id));
+        layers
+    }
+
+    /// Returns the layer an object belongs to, i.e. the layer of its top-most ancestor.
+    ///
+    /// Returns `None` for `StaticLocked` objects, and entities that are not objects.
+    pub fn of_object(&self, entity: Entity) -> Option<&Layer> {
+        let mut current = entity;
+        let (mut object_layer, mut parent) = self.object_query.get(current).ok()?;
+        while let Some(next) = parent {
+            current = next.get();
+            (object_layer, parent) = self.object_query.get(current).ok()?;
+        }
+        let id = object_layer.map_or(DEFAULT_LAYER, |object_layer| object_layer.0);
+        self.get(id)
+            .or_else(|| self.get(DEFAULT_LAYER))
+            .map(|(_, layer)| layer)
+    }
+}
+
+/// System that initializes newly added `Layer` entities, e.g. after loading a level.
+pub(crate) fn setup_new_layers(mut commands: Commands, query: Query<Entity, Added<Layer>>) {
+    for entity in query.iter() {
+        info!("[Layer] ==> Setup new layer");
+        commands
+            .entity(entity)
+            .insert((GameMarker, Saveable, DespawnOnLoad));
+    }
+}
+
+/// System that makes sure the default layer exists, e.g. when loading a level saved before layers were added, and that
+/// the active layer exists.
+pub(crate) fn ensure_layers(
+    mut commands: Commands,
+    layers: Layers,
+    mut active: ResMut<ActiveLayer>,
+) {
+    if layers.get(DEFAULT_LAYER).is_none() {
+        info!("[Layer] ==> Spawning default layer");
+        commands.spawn(Layer::default());
+    }
+    if layers.get(active.0).is_none() && active.0 != DEFAULT_LAYER {
+        active.0 = DEFAULT_LAYER;
+    }
+}
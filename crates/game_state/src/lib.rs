@@ -1,11 +1,29 @@
+mod camera_bookmark;
 mod components;
 mod config;
 mod events;
 mod game_camera;
+mod garden_bounds;
+mod group;
+mod layer;
 mod plugin;
 mod sets;
 mod state;
 
 pub mod prelude {
-    pub use crate::{components::*, config::*, events::*, plugin::*, sets::*, state::*};
+    pub use crate::{
+        camera_bookmark::CameraBookmark,
+        components::*,
+        config::*,
+        events::*,
+        game_camera::{
+            CameraEvent, OrbitCamera, OrbitCameraSettings, OrbitPosition, ScrollCaptured,
+        },
+        garden_bounds::*,
+        group::{Group, GroupRoots},
+        layer::{ActiveLayer, Layer, Layers, ObjectLayer, DEFAULT_LAYER},
+        plugin::*,
+        sets::*,
+        state::*,
+    };
 }
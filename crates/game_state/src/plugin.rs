@@ -2,7 +2,14 @@ use bevy::prelude::*;
 
 use save::prelude::*;
 
-use crate::{game_camera::GameCameraPlugin, prelude::*, state::plugin::StatePlugin};
+use crate::{
+    camera_bookmark::setup_new_bookmarks,
+    game_camera::GameCameraPlugin,
+    group::setup_new_groups,
+    layer::{ensure_layers, setup_new_layers},
+    prelude::*,
+    state::plugin::StatePlugin,
+};
 
 /// Plugin which handles shared components, state and system sets for the game.
 pub struct GameStatePlugin;
@@ -19,10 +26,36 @@ impl Plugin for GameStatePlugin {
                 .chain(),
         )
         .add_plugins((StatePlugin, GameCameraPlugin))
+        .init_resource::<GardenBounds>()
+        .init_resource::<ActiveLayer>()
+        .add_systems(
+            Update,
+            (
+                setup_new_groups,
+                (ensure_layers, setup_new_layers).chain(),
+                setup_new_bookmarks,
+            )
+                .in_set(SetupSet::RigidBody),
+        )
         // Disabled must be registered so that we can add it to scenes before spawning. E.g. scene tools. It should
         // not be saved though.
         .register_type::<Disabled>()
         // PhysicsBody must be registered as saveable so that we can correctly identify and center objects in scenes
-        .register_saveable::<PhysicsBody>();
+        .register_saveable::<PhysicsBody>()
+        // AttachableAnchor must be saved so that attachables can be moved after loading a level
+        .register_saveable::<AttachableAnchor>()
+        // PrefabSource must be saved so that objects can be traced back to their tool config after loading a level
+        .register_saveable::<PrefabSource>()
+        // Locked and Hidden must be saved so that objects stay locked or hidden after loading a level
+        .register_saveable::<Locked>()
+        .register_saveable::<Hidden>()
+        // Group must be saved so that grouped objects stay grouped after loading a level
+        .register_saveable::<Group>()
+        // Layers, and the layer of each object, must be saved so that objects stay organised after loading a level
+        .register_saveable::<Layer>()
+        .register_saveable::<ObjectLayer>()
+        // Camera bookmarks must be saved so that everyone opening the level gets the same viewpoints
+        .register_saveable::<CameraBookmark>()
+        .register_type::<OrbitPosition>();
     }
 }
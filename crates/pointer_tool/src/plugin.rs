@@ -1,46 +1,89 @@
-use bevy::prelude::*;
+use bevy::{input::mouse::MouseWheel, prelude::*};
 use bevy_mod_raycast::prelude::*;
 
 use bevy_helpers::generic_systems::{debug_state_changes, despawn_recursive_with};
 use editor::prelude::*;
 use game_effects::selected::*;
 use game_state::prelude::*;
+use save::prelude::{RollbackBackEvent, RollbackForwardEvent, RollbackSaveEvent, SaveSet};
 
 use crate::types::*;
 
 /// Plugin which handles the "Pointer" tool.
 ///
 /// This lets you select objects and delete them by pressing the "Delete" key.
+///
+/// Hold `EditorAction::ToggleSelection` (shift or control by default) while clicking to add or remove an object from
+/// the selection. Click and drag on empty space to select all objects inside a rectangle. Objects in a `Group` are
+/// selected as a unit.
+///
+/// Click and drag a selected object to move it. Stand-alone objects are moved across the ground, together with any
+/// other selected stand-alone objects. Attachables slide across the surface of their parent, and are only moved when
+/// they are the object being dragged. Dragging an attachable onto another object that accepts attachables moves it to
+/// that object, if the `AttachRules` of the tool it was placed with allow it.
+///
+/// Hold `EditorAction::Rotate` and scroll, or press the rotate keys, to rotate the selected objects. See `RotateInput`.
+///
+/// Moved objects snap to the grid while snapping is enabled in `SnapSettings`. When moving several objects, the dragged
+/// object snaps to the grid and the others keep their position relative to it. Objects on the ground are kept inside
+/// the `GardenBounds`, see `PlacementSettings::bounds_mode`.
+///
+/// Hold `EditorAction::Scale` and scroll while hovering a selected object to scale the selection, within the limits of
+/// the tool each object was placed with. Whether attachables scale with the object they are attached to is controlled
+/// by `ScaleSettings`.
+///
+/// A rollback is saved when each move, rotate or scale gesture finishes. Gestures that are still in progress are
+/// finished when the tool is exited, or when an undo or redo is requested.
 pub struct PointerToolPlugin;
 
 impl Plugin for PointerToolPlugin {
     fn build(&self, app: &mut App) {
-        app.add_state::<PointerToolState>()
+        app.add_editor_tool::<PointerToolState>()
             .add_systems(OnEnter(PointerToolState::Active), setup_tool)
             .add_systems(
                 OnExit(PointerToolState::Active),
-                despawn_recursive_with::<PointerTool>,
+                (
+                    finish_gestures.before(despawn_recursive_with::<PointerTool>),
+                    despawn_recursive_with::<PointerTool>,
+                    despawn_recursive_with::<BoxSelectNode>,
+                ),
             )
             .add_systems(
                 Update,
                 (
                     debug_state_changes::<PointerToolState>,
-                    handle_tool_change_events.run_if(on_event::<ToolChangedEvent>()),
                     (
                         // Run after `EditorCursorSet` to ensure we have the correct cursor status
                         handle_mouse_click
                             .in_set(EditorCursorSet::Click)
                             .run_if(cursor_not_blocked),
+                        update_box_select.after(handle_mouse_click),
+                        update_move_drag.after(handle_mouse_click),
+                        update_rotate_gesture,
+                        update_scale_gesture,
                         handle_delete_keys,
                     )
                         .run_if(in_state(PointerToolState::Active)),
                 ),
+            )
+            .add_systems(
+                PostUpdate,
+                // Save the current gesture before it would be lost by applying a rollback
+                finish_gestures
+                    .before(SaveSet::SaveRollback)
+                    .run_if(in_state(PointerToolState::Active))
+                    .run_if(
+                        on_event::<RollbackBackEvent>().or_else(on_event::<RollbackForwardEvent>()),
+                    ),
             );
     }
 }
 
 const TOOL_NAME: &str = "Pointer Tool";
 
+/// Minimum distance in pixels the cursor must be dragged before a click turns into a box selection or a move
+const DRAG_THRESHOLD: f32 = 4.;
+
 /// State for the tool
 #[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, States)]
 enum PointerToolState {
@@ -49,78 +92,890 @@ enum PointerToolState {
     Active,
 }
 
+impl EditorTool for PointerToolState {
+    const KEY: &'static str = "pointer";
+    const NAME: &'static str = "Pointer";
+    const ACTIVE: Self = PointerToolState::Active;
+    const DEFAULT: bool = true;
+}
+
 /// System that spawns the tool we enter the activated state.
 fn setup_tool(mut commands: Commands) {
     info!("[{TOOL_NAME}] ==> setup");
     commands.spawn((
         Name::new(TOOL_NAME),
         PointerTool,
-        PointerToolSelection(None),
+        RotateGesture::default(),
+        ScaleGesture::default(),
+    ));
+
+    // Spawn the box selection rectangle, hidden until a box selection is dragged
+    commands.spawn((
+        Name::new("Box Select"),
+        BoxSelectNode,
+        NodeBundle {
+            style: Style {
+                display: Display::None,
+                position_type: PositionType::Absolute,
+                border: UiRect::all(Val::Px(1.)),
+                ..default()
+            },
+            background_color: Color::rgba(1., 1., 1., 0.1).into(),
+            border_color: Color::WHITE.into(),
+            ..default()
+        },
     ));
 }
 
 /// Handle click events, try select an entity and mark them as selected.
+///
+/// If an object was clicked, it can then be dragged to move it. If nothing was clicked, a box selection is started
+/// instead.
 #[allow(clippy::too_many_arguments)]
 fn handle_mouse_click(
-    mut query: Query<&mut PointerToolSelection, With<PointerTool>>,
+    mut commands: Commands,
+    query: Query<Entity, With<PointerTool>>,
     buttons: Res<Input<MouseButton>>,
+    input: ActionInput,
     cursor_ray: Res<CursorRay>,
     mut raycast: Raycast,
     family_child_query: Query<&FamilyChild>,
-    pickable_query: Query<(), With<Pickable>>,
+    pickable_query: PickableQuery,
+    windows: Query<&Window>,
+    group_roots: GroupRoots,
+    selection: Res<Selection>,
+    mut select_writer: EventWriter<SelectEvent>,
+) {
+    if !buttons.just_pressed(MouseButton::Left) {
+        return;
+    }
+    let is_modifier = input.pressed(&EditorAction::ToggleSelection);
+
+    let Some(cursor_pos) = windows.get_single().ok().and_then(|w| w.cursor_position()) else {
+        return;
+    };
+
+    for tool_entity in query.iter() {
+        let result = pick_entity_from_raycast(
+            &cursor_ray,
+            &mut raycast,
+            &pickable_query,
+            &family_child_query,
+        );
+        // Objects in a group are picked as a unit
+        match result.map(|(entity, _)| group_roots.root(entity)) {
+            // Toggle the clicked entity in the selection
+            Some(entity) if is_modifier => {
+                let mut entities = selection.entities().to_vec();
+                if selection.contains(entity) {
+                    entities.retain(|selected| *selected != entity);
+                } else {
+                    entities.push(entity);
+                }
+                select_writer.send(SelectEvent(entities));
+            }
+            // Select only the clicked entity, and allow it to be dragged.
+            // NOTE: Clicking an object that is already selected keeps the selection so they can be moved together. The
+            // selection is reduced to the clicked object when the mouse is released without dragging.
+            Some(entity) => {
+                if !selection.contains(entity) {
+                    select_writer.send(SelectEvent(vec![entity]));
+                }
+                commands.entity(tool_entity).insert(MoveDrag {
+                    clicked: entity,
+                    start: cursor_pos,
+                    ground_start: None,
+                    objects: None,
+                });
+            }
+            // Nothing was clicked, start a box selection
+            None => {
+                commands.entity(tool_entity).insert(BoxSelect {
+                    start: cursor_pos,
+                    additive: is_modifier,
+                });
+            }
+        }
+    }
+}
+
+/// System that updates the box selection rectangle while dragging, and selects all objects inside it when the mouse
+/// button is released.
+///
+/// Objects are selected if the origin of any of their pickable meshes is inside the rectangle. Static objects, such as
+/// the background, and `Locked` or `Hidden` objects are never box-selected.
+#[allow(clippy::too_many_arguments)]
+#[allow(clippy::type_complexity)]
+fn update_box_select(
+    mut commands: Commands,
+    query: Query<(Entity, &BoxSelect), With<PointerTool>>,
+    mut node_query: Query<&mut Style, With<BoxSelectNode>>,
+    buttons: Res<Input<MouseButton>>,
+    windows: Query<&Window>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<GameCamera>>,
+    pickable_query: Query<(Entity, &GlobalTransform), With<Pickable>>,
+    family_child_query: Query<&FamilyChild>,
+    static_locked_query: Query<(), With<StaticLocked>>,
+    blocked_query: PickableQuery,
+    group_roots: GroupRoots,
+    selection: Res<Selection>,
     mut select_writer: EventWriter<SelectEvent>,
 ) {
-    if buttons.just_pressed(MouseButton::Left) {
-        for mut selection in query.iter_mut() {
-            let result = pick_entity_from_raycast(
-                &cursor_ray,
-                &mut raycast,
-                &pickable_query,
+    for (tool_entity, box_select) in query.iter() {
+        let camera_and_cursor = get_camera_and_cursor_pos(&windows, &camera_query);
+        let rect = camera_and_cursor
+            .map(|(_, _, cursor_pos)| Rect::from_corners(box_select.start, cursor_pos));
+        let is_dragging = rect.is_some_and(|rect| rect.size().max_element() > DRAG_THRESHOLD);
+
+        // Update the rectangle
+        for mut style in node_query.iter_mut() {
+            match rect {
+                Some(rect) if is_dragging && buttons.pressed(MouseButton::Left) => {
+                    style.display = Display::Flex;
+                    style.left = Val::Px(rect.min.x);
+                    style.top = Val::Px(rect.min.y);
+                    style.width = Val::Px(rect.width());
+                    style.height = Val::Px(rect.height());
+                }
+                _ => style.display = Display::None,
+            }
+        }
+
+        if buttons.pressed(MouseButton::Left) {
+            continue;
+        }
+        commands.entity(tool_entity).remove::<BoxSelect>();
+
+        let Some(((camera, camera_transform, _), rect)) = camera_and_cursor.zip(rect) else {
+            continue;
+        };
+
+        // A click on empty space clears the selection, unless we are adding to it
+        if !is_dragging {
+            if !box_select.additive && !selection.is_empty() {
+                select_writer.send(SelectEvent(Vec::new()));
+            }
+            continue;
+        }
+
+        let mut entities = if box_select.additive {
+            selection.entities().to_vec()
+        } else {
+            Vec::new()
+        };
+        for (entity, transform) in pickable_query.iter() {
+            let Some(parent) = box_select_object(
+                entity,
                 &family_child_query,
-            );
-            let new_entity = result.map(|(entity, _)| entity);
-            if new_entity != selection.0 {
-                select_writer.send(SelectEvent(new_entity));
-                selection.0 = new_entity;
+                &static_locked_query,
+                &blocked_query,
+                &group_roots,
+            ) else {
+                continue;
+            };
+            if entities.contains(&parent) {
+                continue;
+            }
+            let is_inside = camera
+                .world_to_viewport(camera_transform, transform.translation())
+                .is_some_and(|pos| rect.contains(pos));
+            if is_inside {
+                entities.push(parent);
             }
         }
+        info!("[{TOOL_NAME}] ==> box selected {} objects", entities.len());
+        select_writer.send(SelectEvent(entities));
     }
 }
 
-/// Systems which deletes the selected entity when the delete key is pressed.
-fn handle_delete_keys(
-    mut query: Query<&mut PointerToolSelection, With<PointerTool>>,
-    keys: Res<Input<KeyCode>>,
-    mut despawn_writer: EventWriter<DespawnEntityAndRelations>,
-    not_deletable_query: Query<(), With<StaticLocked>>,
+/// Utility that returns the object to select when a pickable mesh is inside the box selection, or `None` if the object
+/// can't be box-selected.
+///
+/// NOTE: Meshes of objects with `ExternalRelations` are top-level entities, so the `FamilyChild` relation must be
+/// resolved before checking whether the object is blocked.
+fn box_select_object(
+    entity: Entity,
+    family_child_query: &Query<&FamilyChild>,
+    static_locked_query: &Query<(), With<StaticLocked>>,
+    blocked_query: &PickableQuery,
+    group_roots: &GroupRoots,
+) -> Option<Entity> {
+    // Find the top-most entity if it's part of a family, and the group it belongs to
+    let object = family_child_query
+        .get(entity)
+        .map_or(entity, |family_child| family_child.0);
+    let parent = group_roots.root(object);
+    let is_blocked = static_locked_query.contains(parent)
+        || is_pick_blocked(object, blocked_query)
+        || is_pick_blocked(parent, blocked_query);
+    (!is_blocked).then_some(parent)
+}
+
+/// Query for objects that can be moved or rotated
+type MovableObjectQuery<'w, 's> = Query<
+    'w,
+    's,
+    (Option<&'static Parent>, Option<&'static ExternalRelations>),
+    (
+        Or<(With<PhysicsBody>, With<Attachable>)>,
+        Without<StaticLocked>,
+        Without<Locked>,
+    ),
+>;
+
+/// System that moves the selected objects while an object is dragged, and saves a rollback when the mouse button is
+/// released.
+#[allow(clippy::too_many_arguments)]
+fn update_move_drag(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut MoveDrag), With<PointerTool>>,
+    buttons: Res<Input<MouseButton>>,
+    windows: Query<&Window>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<GameCamera>>,
+    cursor_ray: Res<CursorRay>,
+    mut raycast: Raycast,
+    family_child_query: Query<&FamilyChild>,
+    pickable_query: PickableQuery,
+    object_query: MovableObjectQuery,
+    (anchor_query, parent_query, attach_targets): (
+        Query<&AttachableAnchor>,
+        Query<&Parent>,
+        AttachTargets,
+    ),
+    target_query: Query<&GlobalTransform, With<AcceptsAttachables>>,
+    mut transform_query: Query<&mut Transform>,
+    // NOTE: Grouped in tuples because systems are limited to 16 parameters
+    (selection, snap_settings, garden_bounds, placement_settings): (
+        Res<Selection>,
+        Res<SnapSettings>,
+        Res<GardenBounds>,
+        Res<PlacementSettings>,
+    ),
     mut select_writer: EventWriter<SelectEvent>,
+    (mut rollback_writer, mut object_changed_writer): (
+        EventWriter<RollbackSaveEvent>,
+        EventWriter<ObjectChanged>,
+    ),
 ) {
-    if keys.just_pressed(KeyCode::Delete) {
-        for mut selection in query.iter_mut() {
-            if let Some(entity) = selection.0 {
-                if not_deletable_query.contains(entity) {
-                    warn!("Entity is not deletable");
-                } else {
-                    despawn_writer.send(DespawnEntityAndRelations(entity));
-                    selection.0 = None;
-                    select_writer.send(SelectEvent(None));
+    for (tool_entity, mut drag) in query.iter_mut() {
+        // Finish the drag when the mouse button is released
+        if !buttons.pressed(MouseButton::Left) {
+            commands.entity(tool_entity).remove::<MoveDrag>();
+            match drag.objects.as_ref() {
+                Some(objects) => {
+                    if finish_move(objects, &mut object_changed_writer) {
+                        rollback_writer.send(RollbackSaveEvent);
+                    }
+                }
+                // The object was clicked without dragging, so reduce the selection to the clicked object
+                None => {
+                    if selection.entities() != [drag.clicked] {
+                        select_writer.send(SelectEvent(vec![drag.clicked]));
+                    }
+                }
+            }
+            continue;
+        }
+
+        let Some((camera, camera_transform, cursor_pos)) =
+            get_camera_and_cursor_pos(&windows, &camera_query)
+        else {
+            continue;
+        };
+
+        // Wait until the cursor has been dragged far enough, then capture the objects and their starting transforms
+        if drag.objects.is_none() {
+            if cursor_pos.distance(drag.start) < DRAG_THRESHOLD {
+                continue;
+            }
+            drag.ground_start = cursor_to_ground(camera, camera_transform, drag.start);
+            let entities = if selection.contains(drag.clicked) {
+                selection.entities().to_vec()
+            } else {
+                vec![drag.clicked]
+            };
+            let clicked = drag.clicked;
+            drag.objects = Some(
+                entities
+                    .iter()
+                    .filter_map(|&entity| {
+                        let anchor = anchor_query.get(entity).ok().copied();
+                        capture_object(
+                            entity,
+                            anchor,
+                            &object_query,
+                            &transform_query.to_readonly(),
+                        )
+                    })
+                    // Attachables are only moved when they are the object being dragged
+                    .filter(|object| {
+                        object.entity == clicked
+                            || matches!(object.kind, CapturedObjectKind::Ground { .. })
+                    })
+                    .collect(),
+            );
+        }
+
+        // Move the objects
+        let ground = cursor_to_ground(camera, camera_transform, cursor_pos);
+        let mut delta = ground
+            .zip(drag.ground_start)
+            .map(|(ground, start)| ground - start);
+
+        // Snap the clicked object to the grid, and move the other objects by the same amount so they keep their layout
+        let clicked_start = drag
+            .objects
+            .iter()
+            .flatten()
+            .find(|object| object.entity == drag.clicked)
+            .map(|object| object.start.translation);
+        if let Some((delta, start)) = delta.as_mut().zip(clicked_start) {
+            *delta = snap_settings.snap_to_grid(start + *delta) - start;
+        }
+
+        // Keep the objects on the ground inside the garden bounds
+        if let Some(delta) = delta.as_mut() {
+            let mut ground_starts = drag
+                .objects
+                .iter()
+                .flatten()
+                .filter(|object| matches!(object.kind, CapturedObjectKind::Ground { .. }))
+                .map(|object| object.start.translation);
+            match placement_settings.bounds_mode {
+                BoundsMode::Clamp => *delta = garden_bounds.clamp_delta(ground_starts, *delta),
+                BoundsMode::Reject => {
+                    if ground_starts.any(|start| {
+                        garden_bounds.contains(start) && !garden_bounds.contains(start + *delta)
+                    }) {
+                        continue;
+                    }
+                }
+            }
+        }
+        for object in drag.objects.iter_mut().flatten() {
+            match &mut object.kind {
+                CapturedObjectKind::Ground { relations } => {
+                    let Some(delta) = delta else {
+                        continue;
+                    };
+                    if let Ok(mut transform) = transform_query.get_mut(object.entity) {
+                        transform.translation = object.start.translation + delta;
+                    }
+                    for (relation, start) in relations.iter() {
+                        if let Ok(mut transform) = transform_query.get_mut(*relation) {
+                            transform.translation = start.translation + delta;
+                        }
+                    }
+                }
+                CapturedObjectKind::Attachable {
+                    parent,
+                    target,
+                    anchor,
+                } => {
+                    let Ok(parent_transform) = target_query.get(*parent) else {
+                        continue;
+                    };
+                    // Only hit objects that the attachable may be attached to, ignoring the attachable itself and
+                    // anything attached to it
+                    let entity = object.entity;
+                    let rules = attach_targets.rules_of(entity);
+                    let hit = pick_entity_from_raycast_filtered(
+                        &cursor_ray,
+                        &mut raycast,
+                        &pickable_query,
+                        &family_child_query,
+                        |hit| {
+                            target_query.contains(hit)
+                                && hit != entity
+                                && !parent_query.iter_ancestors(hit).any(|a| a == entity)
+                                && rules.is_none_or(|(tool, rules)| {
+                                    attach_targets.can_attach(rules, Some(tool), hit, Some(entity))
+                                })
+                        },
+                    );
+                    let Some((hit_entity, intersection)) = hit else {
+                        continue;
+                    };
+                    let Ok(target_transform) = target_query.get(hit_entity) else {
+                        continue;
+                    };
+
+                    // Keep the size of the attachable in the world when it is moved to another object
+                    let world_scale =
+                        parent_transform.compute_transform().scale * object.start.scale;
+                    let mut hit = AttachableHit::from_intersection(
+                        hit_entity,
+                        &intersection,
+                        target_transform,
+                        anchor.distance,
+                        world_scale.x,
+                    );
+                    hit.snap_to_grid(&snap_settings, target_transform);
+                    if let Ok(mut transform) = transform_query.get_mut(entity) {
+                        *transform = hit.local_transform(
+                            anchor.forward,
+                            anchor.spin,
+                            world_scale,
+                            target_transform,
+                        );
+                    }
+                    if hit_entity != *target {
+                        info!("[{TOOL_NAME}] ==> moving attachable to another object");
+                        commands.entity(entity).set_parent(hit_entity);
+                        *target = hit_entity;
+                    }
                 }
             }
         }
     }
 }
 
-/// System that listens for `ToolChangedEvent` events and activates or de-activates the tool.
-fn handle_tool_change_events(
-    mut events: EventReader<ToolChangedEvent>,
-    mut next_state: ResMut<NextState<PointerToolState>>,
-    state: Res<State<PointerToolState>>,
+/// System that rotates the selected objects using the rotate controls, and saves a rollback when the rotate modifier
+/// is released.
+#[allow(clippy::too_many_arguments)]
+fn update_rotate_gesture(
+    mut commands: Commands,
+    mut query: Query<&mut RotateGesture, With<PointerTool>>,
+    mut rotate_input: RotateInput,
+    object_query: MovableObjectQuery,
+    mut anchor_query: Query<&mut AttachableAnchor>,
+    mut transform_query: Query<&mut Transform>,
+    selection: Res<Selection>,
+    mut rollback_writer: EventWriter<RollbackSaveEvent>,
+    mut object_changed_writer: EventWriter<ObjectChanged>,
 ) {
-    for event in events.read() {
-        if let Tool::Pointer = &event.0 {
-            next_state.set(PointerToolState::Active);
-        } else if *state.get() != PointerToolState::Disabled {
-            next_state.set(PointerToolState::Disabled);
+    let angle = rotate_input.read_angle();
+
+    for mut gesture in query.iter_mut() {
+        // Finish the gesture when the modifier is released
+        if !rotate_input.is_active() {
+            if let Some(rotated) = gesture.0.take() {
+                if finish_rotate(
+                    &rotated,
+                    rotate_input.settings(),
+                    &mut object_changed_writer,
+                ) {
+                    rollback_writer.send(RollbackSaveEvent);
+                }
+            }
+            continue;
         }
+        if angle == 0. {
+            continue;
+        }
+
+        // Start a new gesture by capturing the selected objects and their starting transforms
+        let rotated = gesture.0.get_or_insert_with(|| RotatedObjects {
+            angle: 0.,
+            objects: selection
+                .entities()
+                .iter()
+                .filter_map(|&entity| {
+                    let anchor = anchor_query.get(entity).ok().copied();
+                    capture_object(
+                        entity,
+                        anchor,
+                        &object_query,
+                        &transform_query.to_readonly(),
+                    )
+                })
+                .collect(),
+        });
+        rotated.angle += angle;
+        let snapped_angle = rotate_input.settings().snap(rotated.angle);
+
+        // Rotate the objects
+        for object in rotated.objects.iter() {
+            match &object.kind {
+                CapturedObjectKind::Ground { relations } => {
+                    let rotation = Quat::from_rotation_y(snapped_angle);
+                    let origin = object.start.translation;
+                    if let Ok(mut transform) = transform_query.get_mut(object.entity) {
+                        transform.rotation = rotation * object.start.rotation;
+                    }
+                    // Rotate relations around the origin of the object
+                    for (relation, start) in relations.iter() {
+                        if let Ok(mut transform) = transform_query.get_mut(*relation) {
+                            transform.translation =
+                                origin + rotation * (start.translation - origin);
+                            transform.rotation = rotation * start.rotation;
+                        }
+                    }
+                }
+                CapturedObjectKind::Attachable { anchor, .. } => {
+                    let spin = Quat::from_axis_angle(anchor.forward, snapped_angle);
+                    if let Ok(mut transform) = transform_query.get_mut(object.entity) {
+                        transform.rotation = object.start.rotation * spin;
+                    }
+                    // Keep the anchor up to date, so the rotation is kept when the attachable is moved
+                    let new_anchor = AttachableAnchor {
+                        spin: anchor.spin + snapped_angle,
+                        ..*anchor
+                    };
+                    if let Ok(mut entity_anchor) = anchor_query.get_mut(object.entity) {
+                        *entity_anchor = new_anchor;
+                    } else {
+                        commands.entity(object.entity).insert(new_anchor);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// System that scales the selected objects when scrolling with the scale modifier held while hovering a selected
+/// object, and saves a rollback when the modifier is released.
+///
+/// Each object is clamped by the scaling limits of the tool it was placed with. Objects without a `PrefabSource`, or
+/// whose tool doesn't allow scaling, are not scaled.
+#[allow(clippy::too_many_arguments)]
+#[allow(clippy::type_complexity)]
+fn update_scale_gesture(
+    mut query: Query<&mut ScaleGesture, (With<PointerTool>, Without<MoveDrag>)>,
+    mut scroll_events: EventReader<MouseWheel>,
+    input: ActionInput,
+    cursor_status: Res<EditorCursorStatus>,
+    cursor_ray: Res<CursorRay>,
+    mut raycast: Raycast,
+    family_child_query: Query<&FamilyChild>,
+    pickable_query: PickableQuery,
+    object_query: Query<
+        (&PrefabSource, &GlobalTransform, Option<&Parent>),
+        (Without<StaticLocked>, Without<Locked>),
+    >,
+    children_query: Query<&Children>,
+    attachable_query: Query<(), With<Attachable>>,
+    mut transform_query: Query<&mut Transform>,
+    (selection, tool_library, scale_settings, mut scroll_captured): (
+        Res<Selection>,
+        Res<ToolLibrary>,
+        Res<ScaleSettings>,
+        ResMut<ScrollCaptured>,
+    ),
+    // NOTE: Grouped in a tuple because systems are limited to 16 parameters
+    (mut rollback_writer, mut object_changed_writer): (
+        EventWriter<RollbackSaveEvent>,
+        EventWriter<ObjectChanged>,
+    ),
+) {
+    let scroll: f32 = scroll_events.read().map(|event| event.y).sum();
+
+    for mut gesture in query.iter_mut() {
+        // Finish the gesture when the modifier is released
+        // NOTE: This is checked even while the cursor is over the UI, so the gesture always finishes
+        if !input.pressed(&EditorAction::Scale) {
+            if let Some(scaled) = gesture.0.take() {
+                if finish_scale(&scaled, &mut object_changed_writer) {
+                    rollback_writer.send(RollbackSaveEvent);
+                }
+            }
+            continue;
+        }
+
+        // NOTE: Scrolling over the UI is used by the UI, e.g. to scroll lists
+        if *cursor_status == EditorCursorStatus::Blocked {
+            continue;
+        }
+        // Scrolling is used for rotating while the rotate modifier is held
+        if scroll == 0. || input.pressed(&EditorAction::Rotate) {
+            continue;
+        }
+
+        // Only scale when hovering one of the selected objects
+        let hovered = pick_entity_from_raycast(
+            &cursor_ray,
+            &mut raycast,
+            &pickable_query,
+            &family_child_query,
+        );
+        if !hovered.is_some_and(|(entity, _)| selection.contains(entity)) {
+            continue;
+        }
+        // Stop the camera from zooming while scaling
+        scroll_captured.0 = true;
+
+        let mut scaled_entities = Vec::new();
+        for &entity in selection.entities() {
+            let Ok((source, global_transform, parent)) = object_query.get(entity) else {
+                continue;
+            };
+            // Attachables of a selected object are already scaled by their parent
+            if parent.is_some_and(|parent| selection.contains(parent.get())) {
+                continue;
+            }
+            let Some(scaling) = tool_library.get_scaling(&source.tool) else {
+                continue;
+            };
+
+            // Clamp the scale of the object in the world, which is what the tool limits apply to when placing
+            let world_scale = global_transform.compute_transform().scale.x;
+            let new_world_scale = scaling.clamp(world_scale + scroll * scale_settings.scroll_speed);
+            if world_scale <= 0. || new_world_scale == world_scale {
+                continue;
+            }
+            let factor = new_world_scale / world_scale;
+            if let Ok(mut transform) = transform_query.get_mut(entity) {
+                transform.scale *= factor;
+            }
+
+            // Counter-scale attachables so they keep their size in the world
+            if !scale_settings.scale_attachables_with_parent {
+                for child in children_query.get(entity).into_iter().flatten() {
+                    if !attachable_query.contains(*child) {
+                        continue;
+                    }
+                    if let Ok(mut transform) = transform_query.get_mut(*child) {
+                        transform.scale /= factor;
+                    }
+                }
+            }
+            scaled_entities.push(entity);
+        }
+        if scaled_entities.is_empty() {
+            continue;
+        }
+
+        // Start a new gesture, or extend the current one
+        let scaled = gesture.0.get_or_insert_with(|| ScaledObjects {
+            entities: Vec::new(),
+        });
+        for entity in scaled_entities {
+            if !scaled.entities.contains(&entity) {
+                scaled.entities.push(entity);
+            }
+        }
+    }
+}
+
+/// System that finishes the move, rotate and scale gestures that are still in progress, and saves a single rollback
+/// for them.
+///
+/// This runs when the tool is exited, and before an undo or redo is applied, so the changes made by an unfinished
+/// gesture are neither lost nor saved on top of the rolled back state.
+fn finish_gestures(
+    mut commands: Commands,
+    mut query: Query<
+        (
+            Entity,
+            &mut RotateGesture,
+            &mut ScaleGesture,
+            Option<&MoveDrag>,
+        ),
+        With<PointerTool>,
+    >,
+    rotation_settings: Res<RotationSettings>,
+    mut rollback_writer: EventWriter<RollbackSaveEvent>,
+    mut object_changed_writer: EventWriter<ObjectChanged>,
+) {
+    for (tool_entity, mut rotate_gesture, mut scale_gesture, drag) in query.iter_mut() {
+        let mut changed = false;
+        if let Some(objects) = drag.and_then(|drag| drag.objects.as_ref()) {
+            changed |= finish_move(objects, &mut object_changed_writer);
+        }
+        if drag.is_some() {
+            commands.entity(tool_entity).remove::<MoveDrag>();
+        }
+        if let Some(rotated) = rotate_gesture.0.take() {
+            changed |= finish_rotate(&rotated, &rotation_settings, &mut object_changed_writer);
+        }
+        if let Some(scaled) = scale_gesture.0.take() {
+            changed |= finish_scale(&scaled, &mut object_changed_writer);
+        }
+        if changed {
+            rollback_writer.send(RollbackSaveEvent);
+        }
+    }
+}
+
+/// Utility that sends the change events for objects moved by a finished drag. Returns true if any objects were moved.
+fn finish_move(
+    objects: &[CapturedObject],
+    object_changed_writer: &mut EventWriter<ObjectChanged>,
+) -> bool {
+    if objects.is_empty() {
+        return false;
+    }
+    info!("[{TOOL_NAME}] ==> moved {} objects", objects.len());
+    for object in objects.iter() {
+        object_changed_writer.send(ObjectChanged::Transformed(object.entity));
+        if let CapturedObjectKind::Attachable { parent, target, .. } = object.kind {
+            if target != parent {
+                object_changed_writer.send(ObjectChanged::Reparented {
+                    entity: object.entity,
+                    parent: target,
+                });
+            }
+        }
+    }
+    true
+}
+
+/// Utility that sends the change events for objects rotated by a finished rotate gesture. Returns true if the objects
+/// were rotated.
+fn finish_rotate(
+    rotated: &RotatedObjects,
+    settings: &RotationSettings,
+    object_changed_writer: &mut EventWriter<ObjectChanged>,
+) -> bool {
+    if settings.snap(rotated.angle) == 0. {
+        return false;
+    }
+    info!(
+        "[{TOOL_NAME}] ==> rotated {} objects",
+        rotated.objects.len()
+    );
+    for object in rotated.objects.iter() {
+        object_changed_writer.send(ObjectChanged::Transformed(object.entity));
+    }
+    true
+}
+
+/// Utility that sends the change events for objects scaled by a finished scale gesture. Returns true if any objects
+/// were scaled.
+fn finish_scale(
+    scaled: &ScaledObjects,
+    object_changed_writer: &mut EventWriter<ObjectChanged>,
+) -> bool {
+    if scaled.entities.is_empty() {
+        return false;
+    }
+    info!("[{TOOL_NAME}] ==> scaled {} objects", scaled.entities.len());
+    for &entity in scaled.entities.iter() {
+        object_changed_writer.send(ObjectChanged::Transformed(entity));
+    }
+    true
+}
+
+/// Utility that captures an object that can be moved or rotated, along with its current transform.
+///
+/// Returns `None` if the object can't be moved, e.g. because it is `StaticLocked` or `Locked`.
+fn capture_object(
+    entity: Entity,
+    anchor: Option<AttachableAnchor>,
+    object_query: &MovableObjectQuery,
+    transform_query: &Query<&Transform>,
+) -> Option<CapturedObject> {
+    let (parent, relations) = object_query.get(entity).ok()?;
+    let start = *transform_query.get(entity).ok()?;
+    let kind = match parent {
+        Some(parent) => CapturedObjectKind::Attachable {
+            parent: parent.get(),
+            target: parent.get(),
+            anchor: anchor.unwrap_or_default(),
+        },
+        None => CapturedObjectKind::Ground {
+            relations: relations
+                .map(|relations| {
+                    relations
+                        .0
+                        .iter()
+                        .filter_map(|&relation| {
+                            let transform = transform_query.get(relation).ok()?;
+                            Some((relation, *transform))
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+        },
+    };
+    Some(CapturedObject {
+        entity,
+        start,
+        kind,
+    })
+}
+
+/// Utility that projects a cursor position onto the ground plane.
+fn cursor_to_ground(
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+    cursor_pos: Vec2,
+) -> Option<Vec3> {
+    camera
+        .viewport_to_world(camera_transform, cursor_pos)
+        .and_then(|ray| {
+            ray.intersect_plane(Vec3::ZERO, Vec3::Y)
+                .map(|distance| ray.get_point(distance))
+        })
+}
+
+/// Systems which deletes the selected entities when `EditorAction::Delete` is pressed.
+#[allow(clippy::type_complexity)]
+fn handle_delete_keys(
+    input: ActionInput,
+    selection: Res<Selection>,
+    mut despawn_writer: EventWriter<DespawnEntityAndRelations>,
+    not_deletable_query: Query<(), Or<(With<StaticLocked>, With<Locked>)>>,
+    mut select_writer: EventWriter<SelectEvent>,
+) {
+    if !input.just_pressed(&EditorAction::Delete) || selection.is_empty() {
+        return;
+    }
+    for &entity in selection.entities() {
+        if not_deletable_query.contains(entity) {
+            warn!("Entity is not deletable");
+        } else {
+            despawn_writer.send(DespawnEntityAndRelations(entity));
+        }
+    }
+    select_writer.send(SelectEvent(Vec::new()));
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::SystemState;
+
+    use super::*;
+
+    type BoxSelectParams<'w, 's> = (
+        Query<'w, 's, &'static FamilyChild>,
+        Query<'w, 's, (), With<StaticLocked>>,
+        PickableQuery<'w, 's>,
+        GroupRoots<'w, 's>,
+    );
+
+    fn box_select(world: &mut World, entity: Entity) -> Option<Entity> {
+        let mut state: SystemState<BoxSelectParams> = SystemState::new(world);
+        let (family_child_query, static_locked_query, blocked_query, group_roots) =
+            state.get(world);
+        box_select_object(
+            entity,
+            &family_child_query,
+            &static_locked_query,
+            &blocked_query,
+            &group_roots,
+        )
+    }
+
+    #[test]
+    fn test_box_select_family_child() {
+        let mut world = World::new();
+        let root = world.spawn_empty().id();
+        let mesh = world.spawn((Pickable, FamilyChild(root))).id();
+
+        assert_eq!(box_select(&mut world, mesh), Some(root));
+    }
+
+    #[test]
+    fn test_box_select_hidden_family_child() {
+        let mut world = World::new();
+        let root = world.spawn(Hidden).id();
+        let mesh = world.spawn((Pickable, FamilyChild(root))).id();
+
+        assert_eq!(box_select(&mut world, mesh), None);
+    }
+
+    #[test]
+    fn test_box_select_family_child_on_hidden_layer() {
+        let mut world = World::new();
+        world.spawn(Layer {
+            id: 1,
+            visible: false,
+            ..default()
+        });
+        let root = world.spawn(ObjectLayer(1)).id();
+        let mesh = world.spawn((Pickable, FamilyChild(root))).id();
+
+        assert_eq!(box_select(&mut world, mesh), None);
     }
 }
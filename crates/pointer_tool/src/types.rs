@@ -1,7 +1,79 @@
 use bevy::prelude::*;
 
+use game_state::prelude::AttachableAnchor;
+
 #[derive(Component)]
 pub(crate) struct PointerTool;
 
+/// Component added to the tool entity while a box selection is being dragged.
+#[derive(Component)]
+pub(crate) struct BoxSelect {
+    /// Cursor position in the window where the drag started
+    pub start: Vec2,
+    /// Whether to add the boxed objects to the current selection instead of replacing it
+    pub additive: bool,
+}
+
+/// Marker component for the UI node that displays the box selection rectangle.
 #[derive(Component)]
-pub(crate) struct PointerToolSelection(pub Option<Entity>);
+pub(crate) struct BoxSelectNode;
+
+/// Component added to the tool entity while the mouse button is held down on an object, so it can be dragged.
+#[derive(Component)]
+pub(crate) struct MoveDrag {
+    /// The object that was clicked
+    pub clicked: Entity,
+    /// Cursor position in the window where the drag started
+    pub start: Vec2,
+    /// Point on the ground plane where the drag started
+    pub ground_start: Option<Vec3>,
+    /// The objects being moved. This is `None` until the cursor has been dragged far enough to start moving.
+    pub objects: Option<Vec<CapturedObject>>,
+}
+
+/// Component on the tool entity that tracks the current rotate gesture. It is `None` when not rotating.
+#[derive(Component, Default)]
+pub(crate) struct RotateGesture(pub Option<RotatedObjects>);
+
+/// The objects being rotated by a rotate gesture.
+pub(crate) struct RotatedObjects {
+    /// Total angle in radians, before being rounded to the rotation increment
+    pub angle: f32,
+    pub objects: Vec<CapturedObject>,
+}
+
+/// Component on the tool entity that tracks the current scale gesture. It is `None` when not scaling. A gesture lasts
+/// for as long as `EditorAction::Scale` is held down.
+#[derive(Component, Default)]
+pub(crate) struct ScaleGesture(pub Option<ScaledObjects>);
+
+/// The objects being scaled by a scale gesture.
+pub(crate) struct ScaledObjects {
+    pub entities: Vec<Entity>,
+}
+
+/// An object being moved or rotated, along with its transform when the action started.
+pub(crate) struct CapturedObject {
+    pub entity: Entity,
+    pub start: Transform,
+    pub kind: CapturedObjectKind,
+}
+
+/// The type of object being moved or rotated.
+pub(crate) enum CapturedObjectKind {
+    /// Stand-alone objects are moved across the ground, and rotated around the Y axis. Their external relations are
+    /// moved with them.
+    Ground {
+        /// External relations and their transforms when the action started
+        relations: Vec<(Entity, Transform)>,
+    },
+    /// Attachables are moved across the surface of their parent, and rotated around their anchor's forward axis. They
+    /// can be dragged onto another object that accepts attachables, which they are then re-parented to.
+    Attachable {
+        /// The parent when the action started
+        parent: Entity,
+        /// The object the attachable is currently attached to
+        target: Entity,
+        anchor: AttachableAnchor,
+    },
+}
@@ -0,0 +1,273 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+
+use bevy_scene_utils::commands::ExtractSceneToChildCommand;
+use editor::prelude::*;
+use game_state::prelude::*;
+
+use crate::{brush::BrushSettings, types::*};
+
+/// Plugin which handles placing arrays of copies with prefab tools.
+///
+/// While the array is enabled, clicking places copies of the prefab along a line, in a grid or around a circle,
+/// starting at the cursor and following its rotation. A preview of every copy is shown with the cursor. The array is
+/// toggled and adjusted with the `EditorAction::ToggleArray` family of actions, and configured with the `ArraySettings`
+/// resource.
+///
+/// NOTE: Only objects placed on the ground are placed in arrays, attachables are always placed one at a time.
+pub(crate) struct ArrayPlugin;
+
+impl Plugin for ArrayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ArraySettings>().add_systems(
+            Update,
+            (handle_array_keys, update_array_preview)
+                .chain()
+                .run_if(in_game),
+        );
+    }
+}
+
+/// Maximum number of copies along each side of an array
+const MAX_COUNT: usize = 50;
+
+/// Amount the spacing changes with `EditorAction::IncreaseArraySpacing` and `EditorAction::DecreaseArraySpacing`
+const SPACING_STEP: f32 = 0.25;
+
+/// The layout of the copies in an array
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ArrayShape {
+    /// Copies along the cursor's X axis
+    #[default]
+    Line,
+    /// Rows of copies along the cursor's X axis, stacked along its Z axis
+    Grid,
+    /// Copies around a circle, which passes through the cursor
+    Circle,
+}
+
+impl ArrayShape {
+    fn next(&self) -> Self {
+        match self {
+            ArrayShape::Line => ArrayShape::Grid,
+            ArrayShape::Grid => ArrayShape::Circle,
+            ArrayShape::Circle => ArrayShape::Line,
+        }
+    }
+}
+
+/// Resource controlling the arrays placed by prefab tools.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct ArraySettings {
+    pub enabled: bool,
+    pub shape: ArrayShape,
+    /// Number of copies in a line or circle, or in each row of a grid
+    pub count: usize,
+    /// Number of rows in a grid
+    pub rows: usize,
+    /// Distance between neighbouring copies in world units
+    pub spacing: f32,
+}
+
+impl Default for ArraySettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            shape: ArrayShape::default(),
+            count: 5,
+            rows: 3,
+            spacing: 2.,
+        }
+    }
+}
+
+impl ArraySettings {
+    /// Returns the offset of each copy in world units, relative to the cursor and before applying its rotation. The
+    /// first copy is always at the cursor.
+    pub fn offsets(&self) -> Vec<Vec3> {
+        let count = self.count.max(1);
+        match self.shape {
+            ArrayShape::Line => (0..count)
+                .map(|i| Vec3::X * self.spacing * i as f32)
+                .collect(),
+            ArrayShape::Grid => (0..self.rows.max(1))
+                .flat_map(|row| (0..count).map(move |column| (row, column)))
+                .map(|(row, column)| Vec3::new(column as f32, 0., row as f32) * self.spacing)
+                .collect(),
+            ArrayShape::Circle => {
+                // Choose the radius so that neighbouring copies are `spacing` apart along the circle
+                let radius = self.spacing * count as f32 / TAU;
+                let center = Vec3::NEG_X * radius;
+                (0..count)
+                    .map(|i| {
+                        let angle = TAU * i as f32 / count as f32;
+                        center + Vec3::new(angle.cos(), 0., angle.sin()) * radius
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// Returns the world transform of each copy placed with the cursor at `transform`.
+    pub fn transforms(&self, transform: &Transform) -> Vec<Transform> {
+        self.offsets()
+            .into_iter()
+            .map(|offset| {
+                transform.mul_transform(Transform::from_translation(offset / transform.scale))
+            })
+            .collect()
+    }
+}
+
+/// Component added to prefab tool cursors containing the offsets of the copies in the preview.
+#[derive(Component, Default)]
+pub(crate) struct ArrayPreview(Vec<Vec3>);
+
+/// A copy in the preview, containing its offset in world units
+#[derive(Component)]
+struct ArrayPreviewCopy(Vec3);
+
+/// System that toggles and adjusts the array using the keys in the `InputMap`.
+fn handle_array_keys(
+    input: ActionInput,
+    mut settings: ResMut<ArraySettings>,
+    mut brush_settings: ResMut<BrushSettings>,
+) {
+    let mut new_settings = settings.clone();
+    if input.just_pressed(&EditorAction::ToggleArray) {
+        new_settings.enabled = !new_settings.enabled;
+    }
+    if input.just_pressed(&EditorAction::CycleArrayShape) {
+        new_settings.shape = new_settings.shape.next();
+    }
+    if input.just_pressed(&EditorAction::IncreaseArrayCount) {
+        new_settings.count = (new_settings.count + 1).min(MAX_COUNT);
+    }
+    if input.just_pressed(&EditorAction::DecreaseArrayCount) {
+        new_settings.count = new_settings.count.saturating_sub(1).max(1);
+    }
+    if input.just_pressed(&EditorAction::IncreaseArrayRows) {
+        new_settings.rows = (new_settings.rows + 1).min(MAX_COUNT);
+    }
+    if input.just_pressed(&EditorAction::DecreaseArrayRows) {
+        new_settings.rows = new_settings.rows.saturating_sub(1).max(1);
+    }
+    if input.just_pressed(&EditorAction::IncreaseArraySpacing) {
+        new_settings.spacing += SPACING_STEP;
+    }
+    if input.just_pressed(&EditorAction::DecreaseArraySpacing) {
+        new_settings.spacing = (new_settings.spacing - SPACING_STEP).max(SPACING_STEP);
+    }
+
+    if *settings != new_settings {
+        info!("[Array] ==> {new_settings:?}");
+        // The brush and the array both use clicks, so only one of them can be enabled
+        if new_settings.enabled && brush_settings.enabled {
+            brush_settings.enabled = false;
+        }
+        *settings = new_settings;
+    }
+}
+
+/// System that spawns a preview of every copy in the array as children of the cursor, and keeps them spaced apart
+/// while the cursor is scaled.
+#[allow(clippy::type_complexity)]
+fn update_array_preview(
+    mut commands: Commands,
+    mut query: Query<
+        (Entity, &PrefabToolCursor, &Transform, &mut ArrayPreview),
+        (With<ObjectCursor>, With<PrefabToolOk>),
+    >,
+    mut copy_query: Query<
+        (Entity, &ArrayPreviewCopy, &Parent, &mut Transform),
+        Without<ArrayPreview>,
+    >,
+    settings: Res<ArraySettings>,
+) {
+    for (entity, prefab_tool, transform, mut preview) in query.iter_mut() {
+        // The first copy is the cursor itself
+        let offsets: Vec<Vec3> = if settings.enabled {
+            settings.offsets().into_iter().skip(1).collect()
+        } else {
+            Vec::new()
+        };
+
+        // Re-spawn the copies when the layout changes
+        if preview.0 != offsets {
+            for (copy_entity, _, parent, _) in copy_query.iter() {
+                if parent.get() == entity {
+                    commands.entity(copy_entity).despawn_recursive();
+                }
+            }
+            for offset in offsets.iter() {
+                let copy_entity = commands
+                    .spawn((
+                        Name::new("Array Copy"),
+                        ArrayPreviewCopy(*offset),
+                        OnPrefabTool,
+                        GameMarker,
+                        SpatialBundle::from_transform(Transform::from_translation(
+                            *offset / transform.scale,
+                        )),
+                    ))
+                    .set_parent(entity)
+                    .id();
+                commands.add(ExtractSceneToChildCommand {
+                    scene: prefab_tool.scene_handle.clone(),
+                    entity: copy_entity,
+                    child_bundle: (Name::new("Array Copy Scene"), OnPrefabTool, GameMarker),
+                    scene_bundle: (Disabled, GameMarker, OnPrefabTool),
+                });
+            }
+            preview.0 = offsets;
+            continue;
+        }
+
+        // Keep the offsets in world units while the cursor is scaled
+        for (_, copy, parent, mut copy_transform) in copy_query.iter_mut() {
+            if parent.get() == entity {
+                let translation = copy.0 / transform.scale;
+                if copy_transform.translation != translation {
+                    copy_transform.translation = translation;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_offsets() {
+        let settings = ArraySettings {
+            shape: ArrayShape::Grid,
+            count: 3,
+            rows: 2,
+            spacing: 2.,
+            ..default()
+        };
+        let offsets = settings.offsets();
+        assert_eq!(offsets.len(), 6);
+        assert_eq!(offsets[0], Vec3::ZERO);
+        assert_eq!(offsets[5], Vec3::new(4., 0., 2.));
+    }
+
+    #[test]
+    fn test_circle_offsets_are_evenly_spaced() {
+        let settings = ArraySettings {
+            shape: ArrayShape::Circle,
+            count: 8,
+            spacing: 1.,
+            ..default()
+        };
+        let offsets = settings.offsets();
+        assert!(offsets[0].abs_diff_eq(Vec3::ZERO, 1e-5));
+        let chord = offsets[0].distance(offsets[1]);
+        for (a, b) in offsets.iter().zip(offsets.iter().cycle().skip(1)) {
+            assert!((a.distance(*b) - chord).abs() < 1e-4);
+        }
+    }
+}
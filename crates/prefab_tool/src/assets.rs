@@ -18,6 +18,21 @@ pub struct PrefabToolAssetAttachable {
     /// The forward direction of the object. Orients the object so this vector faces in the direction of the surface
     /// normal that it is placed on.
     pub forward: Vec3,
+    /// Kinds of objects the attachable may be attached to. Defaults to any object that accepts attachables.
+    #[serde(default)]
+    pub attach_to: Vec<PrefabToolAssetAttachTarget>,
+    /// Maximum number of this attachable on a single object. Defaults to no limit.
+    #[serde(default)]
+    pub max_per_parent: Option<usize>,
+}
+
+/// A kind of object that an attachable may be attached to.
+#[derive(Debug, Serialize, Deserialize)]
+pub enum PrefabToolAssetAttachTarget {
+    /// Objects with the component with this type path, e.g. `Component("tree::components::Tree")`
+    Component(String),
+    /// Objects placed with a tool that has this tag, e.g. `Tag("tree")`
+    Tag(String),
 }
 
 /// The type of prefab tool, controlling how it is placed into the world.
@@ -45,6 +60,14 @@ pub struct PrefabToolAsset {
 
     /// Optional min and max scaling values. A value of `None` will disable scaling.
     pub scaling: Option<PrefabToolAssetScaling>,
+
+    /// Whether the object may be placed overlapping other objects. Defaults to false.
+    #[serde(default)]
+    pub allow_overlap: bool,
+
+    /// Tags describing the kind of object, which attachables can be restricted to. Defaults to no tags.
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
 impl PrefabToolAsset {
@@ -0,0 +1,355 @@
+use std::f32::consts::{PI, TAU};
+
+use bevy::prelude::*;
+use bevy_mod_raycast::prelude::*;
+use rand::Rng;
+
+use editor::prelude::*;
+use game_state::prelude::*;
+use save::prelude::{RollbackSaveEvent, Saveable};
+
+use crate::{array::ArraySettings, commands::SpawnPrefabCommand, types::*};
+
+/// Plugin which handles the scatter brush of prefab tools.
+///
+/// While the brush is enabled, clicking and dragging scatters copies of the prefab under a circular brush instead of
+/// placing a single object. Objects are scattered on the ground, and attachables on the surfaces under the brush. The
+/// brush is toggled with `EditorAction::ToggleBrush`, and configured with the `BrushSettings` resource.
+///
+/// Each stroke saves a single rollback when the mouse is released, or when the tool is exited mid-stroke, so the whole
+/// stroke is undone in one step.
+pub(crate) struct BrushPlugin;
+
+impl Plugin for BrushPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BrushSettings>().add_systems(
+            Update,
+            (
+                handle_toggle_key.run_if(in_game),
+                update_brush_stroke
+                    .run_if(in_game)
+                    .after(EditorCursorSet::Click),
+            ),
+        );
+    }
+}
+
+/// Resource controlling the scatter brush of prefab tools.
+#[derive(Resource, Debug, Clone)]
+pub struct BrushSettings {
+    pub enabled: bool,
+    /// Radius of the brush in world units
+    pub radius: f32,
+    /// Number of objects scattered per square world unit each time the brush is stamped. The brush is stamped when the
+    /// stroke starts, and every time it has moved further than its radius.
+    pub density: f32,
+    /// Minimum distance between scattered objects and any other objects
+    pub min_spacing: f32,
+    /// Whether scattered objects are given a random rotation around their up axis
+    pub random_yaw: bool,
+    /// Whether scattered objects are given a random scale within the scaling limits of the tool
+    pub random_scale: bool,
+}
+
+impl Default for BrushSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            radius: 1.5,
+            density: 0.5,
+            min_spacing: 0.75,
+            random_yaw: true,
+            random_scale: true,
+        }
+    }
+}
+
+/// Component added to prefab tool cursors containing the current brush stroke, if any.
+#[derive(Component, Default)]
+pub(crate) struct BrushStroke(Option<Stroke>);
+
+pub(crate) struct Stroke {
+    /// Where the brush was last stamped
+    last_stamp: Option<Vec3>,
+    /// World positions of the objects placed during this stroke
+    placed: Vec<Vec3>,
+}
+
+/// Where the brush is pointing
+enum BrushTarget {
+    Ground(Vec3),
+    Surface { position: Vec3, normal: Vec3 },
+}
+
+impl BrushTarget {
+    fn position(&self) -> Vec3 {
+        match self {
+            BrushTarget::Ground(position) => *position,
+            BrushTarget::Surface { position, .. } => *position,
+        }
+    }
+
+    fn normal(&self) -> Vec3 {
+        match self {
+            BrushTarget::Ground(_) => Vec3::Y,
+            BrushTarget::Surface { normal, .. } => *normal,
+        }
+    }
+}
+
+/// System that toggles the brush with `EditorAction::ToggleBrush`
+fn handle_toggle_key(
+    input: ActionInput,
+    mut settings: ResMut<BrushSettings>,
+    mut array_settings: ResMut<ArraySettings>,
+) {
+    if input.just_pressed(&EditorAction::ToggleBrush) {
+        settings.enabled = !settings.enabled;
+        info!("[Brush] ==> enabled: {}", settings.enabled);
+        // The brush and the array both use clicks, so only one of them can be enabled
+        if settings.enabled && array_settings.enabled {
+            array_settings.enabled = false;
+        }
+    }
+}
+
+/// System that finishes the current brush stroke when the tool is exited, so the objects it placed are saved.
+pub(crate) fn finish_brush_stroke(
+    mut query: Query<(&PrefabToolCursor, &mut BrushStroke)>,
+    mut rollback_writer: EventWriter<RollbackSaveEvent>,
+) {
+    for (prefab_tool, mut brush_stroke) in query.iter_mut() {
+        if let Some(stroke) = brush_stroke.0.take() {
+            finish_stroke(&prefab_tool.name, &stroke, &mut rollback_writer);
+        }
+    }
+}
+
+/// Utility that saves a single rollback for a finished stroke, if it placed any objects.
+fn finish_stroke(
+    tool_name: &str,
+    stroke: &Stroke,
+    rollback_writer: &mut EventWriter<RollbackSaveEvent>,
+) {
+    if stroke.placed.is_empty() {
+        return;
+    }
+    info!(
+        "[Brush - {tool_name}] ==> scattered {} objects",
+        stroke.placed.len()
+    );
+    rollback_writer.send(RollbackSaveEvent);
+}
+
+/// System that draws the brush, and scatters objects under it while the mouse button is held down.
+#[allow(clippy::too_many_arguments)]
+#[allow(clippy::type_complexity)]
+fn update_brush_stroke(
+    mut commands: Commands,
+    mut query: Query<
+        (
+            &PrefabToolCursor,
+            &Transform,
+            Option<&AttachableCursor>,
+            &mut BrushStroke,
+        ),
+        With<PrefabToolOk>,
+    >,
+    buttons: Res<Input<MouseButton>>,
+    cursor_ray: Res<CursorRay>,
+    mut raycast: Raycast,
+    pickable_query: PickableQuery,
+    family_child_query: Query<&FamilyChild>,
+    target_query: Query<&GlobalTransform, With<AcceptsAttachables>>,
+    attach_targets: AttachTargets,
+    existing_query: Query<&GlobalTransform, (With<Saveable>, Without<StaticLocked>)>,
+    (settings, rotation_settings, garden_bounds, cursor_status): (
+        Res<BrushSettings>,
+        Res<RotationSettings>,
+        Res<GardenBounds>,
+        Res<EditorCursorStatus>,
+    ),
+    mut gizmos: Gizmos,
+    mut rollback_writer: EventWriter<RollbackSaveEvent>,
+) {
+    for (prefab_tool, transform, attachable_cursor, mut brush_stroke) in query.iter_mut() {
+        // Finish the stroke when the mouse button is released
+        if !settings.enabled || !buttons.pressed(MouseButton::Left) {
+            if let Some(stroke) = brush_stroke.0.take() {
+                finish_stroke(&prefab_tool.name, &stroke, &mut rollback_writer);
+            }
+        }
+        if !settings.enabled {
+            continue;
+        }
+
+        // Find where the brush is pointing
+        let target = match attachable_cursor {
+            Some(cursor) => pick_entity_from_raycast_filtered(
+                &cursor_ray,
+                &mut raycast,
+                &pickable_query,
+                &family_child_query,
+                |entity| {
+                    target_query.contains(entity) && attach_targets.matches(&cursor.rules, entity)
+                },
+            )
+            .map(|(_, intersection)| BrushTarget::Surface {
+                position: intersection.position(),
+                normal: intersection.normal().normalize(),
+            }),
+            None => Some(BrushTarget::Ground(transform.translation)),
+        };
+        let Some(target) = target else {
+            continue;
+        };
+        gizmos.circle(
+            target.position(),
+            target.normal(),
+            settings.radius,
+            Color::WHITE,
+        );
+
+        // Start a stroke when clicking
+        if buttons.just_pressed(MouseButton::Left) && *cursor_status == EditorCursorStatus::Active {
+            brush_stroke.0 = Some(Stroke {
+                last_stamp: None,
+                placed: Vec::new(),
+            });
+        }
+        let Some(stroke) = brush_stroke.0.as_mut() else {
+            continue;
+        };
+
+        // Stamp the brush when it has moved far enough
+        let center = target.position();
+        if stroke
+            .last_stamp
+            .is_some_and(|last_stamp| last_stamp.distance(center) < settings.radius)
+        {
+            continue;
+        }
+        stroke.last_stamp = Some(center);
+
+        let existing: Vec<Vec3> = existing_query
+            .iter()
+            .map(|transform| transform.translation())
+            .collect();
+        let count = (settings.density * PI * settings.radius * settings.radius)
+            .round()
+            .max(1.) as usize;
+        let mut rng = rand::thread_rng();
+        // Objects attached this frame, which are not spawned yet so they aren't counted by `AttachTargets`
+        let mut attached: Vec<Entity> = Vec::new();
+
+        for _ in 0..count {
+            // Pick a random point in the brush, with a random rotation and scale
+            let offset = Vec2::from_angle(rng.gen_range(0.0..TAU))
+                * settings.radius
+                * rng.gen::<f32>().sqrt();
+            let yaw = rng.gen_range(0.0..TAU);
+            let scale = match prefab_tool.scaling.as_ref() {
+                Some(scaling) if settings.random_scale && scaling.min < scaling.max => {
+                    rng.gen_range(scaling.min..=scaling.max)
+                }
+                _ => transform.scale.x,
+            };
+
+            let spawn = match &target {
+                BrushTarget::Ground(center) => {
+                    let position = *center + Vec3::new(offset.x, 0., offset.y);
+                    let rotation = if settings.random_yaw {
+                        Quat::from_rotation_y(yaw)
+                    } else {
+                        transform.rotation
+                    };
+                    garden_bounds.contains(position).then(|| {
+                        let transform = Transform::from_translation(position)
+                            .with_rotation(rotation)
+                            .with_scale(Vec3::splat(scale));
+                        (position, transform, None, None)
+                    })
+                }
+                BrushTarget::Surface { position, normal } => {
+                    // NOTE: Safe to unwrap, only attachable cursors target surfaces
+                    let cursor = attachable_cursor.unwrap();
+                    let spin = if settings.random_yaw {
+                        yaw
+                    } else {
+                        rotation_settings.snap(cursor.rotation)
+                    };
+
+                    // Cast a ray towards the surface, from above the point in the brush
+                    let (u, v) = normal.any_orthonormal_pair();
+                    let point = *position + u * offset.x + v * offset.y;
+                    let ray = Ray3d::new(point + *normal * settings.radius, -*normal);
+                    pick_entity_from_ray(
+                        ray,
+                        &mut raycast,
+                        &pickable_query,
+                        &family_child_query,
+                        |entity| {
+                            target_query.contains(entity)
+                                && attach_targets.matches(&cursor.rules, entity)
+                        },
+                    )
+                    .filter(|(entity, _)| {
+                        let (Some(max), Some(tool)) =
+                            (cursor.rules.max_per_parent, cursor.tool.as_deref())
+                        else {
+                            return true;
+                        };
+                        let pending = attached.iter().filter(|other| *other == entity).count();
+                        attach_targets.count(tool, *entity, None) + pending < max
+                    })
+                    .and_then(|(entity, intersection)| {
+                        let target_transform = target_query.get(entity).ok()?;
+                        let hit = AttachableHit::from_intersection(
+                            entity,
+                            &intersection,
+                            target_transform,
+                            cursor.distance,
+                            scale,
+                        );
+                        let transform = hit.local_transform(
+                            cursor.forward,
+                            spin,
+                            Vec3::splat(scale),
+                            target_transform,
+                        );
+                        let anchor = AttachableAnchor {
+                            distance: cursor.distance,
+                            forward: cursor.forward,
+                            spin,
+                        };
+                        Some((hit.world_pos, transform, Some(entity), Some(anchor)))
+                    })
+                }
+            };
+            let Some((position, transform, parent, anchor)) = spawn else {
+                continue;
+            };
+
+            // Keep the objects spaced apart
+            let too_close = existing
+                .iter()
+                .chain(stroke.placed.iter())
+                .any(|other| other.distance(position) < settings.min_spacing);
+            if too_close {
+                continue;
+            }
+
+            stroke.placed.push(position);
+            attached.extend(parent);
+            commands.add(SpawnPrefabCommand {
+                tool_name: prefab_tool.name.clone(),
+                scene_handle: prefab_tool.scene_handle.clone(),
+                transform,
+                parent,
+                anchor,
+                source: prefab_tool.key.clone(),
+                send_result: false,
+            });
+        }
+    }
+}
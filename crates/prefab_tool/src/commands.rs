@@ -1,6 +1,8 @@
 use bevy::{ecs::system::Command, prelude::*};
 
 use bevy_scene_utils::write_dynamic_scene_asset_to_world;
+use editor::prelude::ObjectChanged;
+use game_state::prelude::{ActiveLayer, AttachableAnchor, FamilyChild, ObjectLayer, PrefabSource};
 use save::prelude::*;
 
 use crate::types::PrefabToolResult;
@@ -14,10 +16,18 @@ pub(crate) struct SpawnPrefabCommand {
     pub scene_handle: Handle<DynamicScene>,
     pub transform: Transform,
     pub parent: Option<Entity>,
+    /// Anchor to add to the top-level entities when placing attachables
+    pub anchor: Option<AttachableAnchor>,
+    /// Key of the tool in the `ToolLibrary`, added to the top-level entities as a `PrefabSource`
+    pub source: Option<String>,
+    /// Whether to send a `PrefabToolResult` once spawned, which saves a rollback. Errors are always sent. Brush strokes
+    /// spawn many prefabs, and save a single rollback when the stroke is finished instead.
+    pub send_result: bool,
 }
 
 impl Command for SpawnPrefabCommand {
     fn apply(self, world: &mut World) {
+        let active_layer = world.resource::<ActiveLayer>().0;
         let result = write_dynamic_scene_asset_to_world(self.scene_handle, world, |entity_mut| {
             // IMPORTANT: Mark all entities as saveable so they can be saved right away. We send a `RollbackSave` event at
             // the end of the command, so if the entities aren't marked as saveable they won't be saved.
@@ -29,6 +39,21 @@ impl Command for SpawnPrefabCommand {
                 if let Some(mut entity_transform) = entity_mut.get_mut::<Transform>() {
                     *entity_transform = self.transform.mul_transform(*entity_transform);
                 }
+
+                // Keep track of the tool the object was placed with, unless the scene already contains a source (e.g.
+                // when placing a duplicated object)
+                if let Some(source) = self.source.as_ref() {
+                    if !entity_mut.contains::<PrefabSource>() {
+                        entity_mut.insert(PrefabSource {
+                            tool: source.clone(),
+                        });
+                    }
+                }
+
+                // Stand-alone objects are placed on the active layer, even if the scene was copied from another layer
+                if self.parent.is_none() {
+                    entity_mut.insert(ObjectLayer(active_layer));
+                }
             }
 
             // NOTE: if using a physics engine, you might want to clear any `Velocity` components in the scene
@@ -41,14 +66,34 @@ impl Command for SpawnPrefabCommand {
             if let Some(parent) = self.parent {
                 if entity_mut.get::<Parent>().is_none() {
                     entity_mut.set_parent(parent);
+                    if let Some(anchor) = self.anchor {
+                        entity_mut.insert(anchor);
+                    }
                 }
             }
         });
 
         // Send events with the result so we can handle the result in the tool plugin
         match result {
-            Ok(_) => {
-                world.send_event(PrefabToolResult(Ok(self.tool_name.clone())));
+            Ok(entities) => {
+                // Report each spawned root object, i.e. those whose parent was not spawned as part of the scene.
+                // NOTE: Entities with a `FamilyChild` are relations of another object, not objects themselves
+                let roots: Vec<Entity> = entities
+                    .iter()
+                    .filter(|entity| !world.entity(**entity).contains::<FamilyChild>())
+                    .filter(|entity| match world.get::<Parent>(**entity) {
+                        Some(parent) => !entities.contains(&parent.get()),
+                        None => true,
+                    })
+                    .copied()
+                    .collect();
+                for root in roots {
+                    world.send_event(ObjectChanged::Spawned(root));
+                }
+
+                if self.send_result {
+                    world.send_event(PrefabToolResult(Ok(self.tool_name.clone())));
+                }
             }
             Err(err) => {
                 world.send_event(PrefabToolResult(Err(format!(
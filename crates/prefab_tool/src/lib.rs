@@ -1,8 +1,15 @@
+mod array;
 mod assets;
+mod brush;
 mod commands;
 mod plugin;
 mod types;
 
 pub mod prelude {
-    pub use crate::{assets::*, plugin::*};
+    pub use crate::{
+        array::{ArraySettings, ArrayShape},
+        assets::*,
+        brush::BrushSettings,
+        plugin::*,
+    };
 }
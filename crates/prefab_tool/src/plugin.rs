@@ -4,24 +4,36 @@ use bevy_common_assets::ron::RonAssetPlugin;
 use bevy_helpers::generic_systems::{debug_state_changes, despawn_recursive_with};
 use bevy_scene_utils::commands::ExtractSceneToChildCommand;
 use editor::prelude::*;
+use game_effects::transparency::{TransparentMaterial, TransparentMaterialTint};
 use game_state::prelude::*;
 use save::prelude::RollbackSaveEvent;
 
-use crate::{assets::*, commands::*, types::*};
+use crate::{array::*, assets::*, brush::*, commands::*, types::*};
 
 /// Plugin which handles prefab tools defined by `ron` files
+///
+/// Prefab tools place a single object per click, or scatter many objects at once while the brush is enabled, see
+/// `BrushSettings`. Objects can also be placed as lines, grids or circles of copies while the array is enabled, see
+/// `ArraySettings`.
 pub struct PrefabToolPlugin;
 
 impl Plugin for PrefabToolPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<PrefabToolResult>()
             .add_state::<PrefabToolState>()
-            .add_plugins(RonAssetPlugin::<PrefabToolAsset>::new(&["tool.ron"]))
+            .add_plugins((
+                RonAssetPlugin::<PrefabToolAsset>::new(&["tool.ron"]),
+                BrushPlugin,
+                ArrayPlugin,
+            ))
             .add_systems(OnEnter(PrefabToolState::Active), setup_tool)
             .add_systems(OnEnter(PrefabToolState::Reload), on_enter_reload)
             .add_systems(
                 OnExit(PrefabToolState::Active),
-                despawn_recursive_with::<OnPrefabTool>,
+                (
+                    finish_brush_stroke.before(despawn_recursive_with::<OnPrefabTool>),
+                    despawn_recursive_with::<OnPrefabTool>,
+                ),
             )
             .add_systems(
                 Update,
@@ -34,7 +46,12 @@ impl Plugin for PrefabToolPlugin {
                     )
                         .after(EditorCursorSet::Click),
                     handle_asset_loading.run_if(in_state(PrefabToolState::Active)),
-                    handle_mouse_scroll.run_if(in_game),
+                    // NOTE: Scrolling over the UI is used by the UI, e.g. to scroll lists
+                    handle_mouse_scroll
+                        .run_if(in_game)
+                        .run_if(cursor_not_blocked),
+                    handle_rotate_input.run_if(in_game),
+                    update_cursor_tint.after(EditorCursorSet::Validate),
                     handle_results.run_if(on_event::<PrefabToolResult>()),
                 ),
             );
@@ -42,7 +59,6 @@ impl Plugin for PrefabToolPlugin {
 }
 
 const TOOL_NAME: &str = "Prefab Tool";
-const SCALE_SPEED: f32 = 0.01;
 
 /// State for the tool
 #[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, States)]
@@ -69,9 +85,13 @@ fn setup_tool(mut commands: Commands, config: Res<PrefabToolConfig>) {
             OnPrefabTool,
             PrefabToolCursor {
                 name: config.0.name.clone(),
+                key: config.0.key.clone(),
                 scene_handle: config.0.scene.clone(),
                 scaling: config.0.scaling.clone(),
             },
+            CursorPlacement::new(config.0.allow_overlap),
+            BrushStroke::default(),
+            ArrayPreview::default(),
             SpatialBundle::from_transform(
                 Transform::IDENTITY.with_scale(Vec3::splat(config.0.initial_scale)),
             ),
@@ -80,14 +100,17 @@ fn setup_tool(mut commands: Commands, config: Res<PrefabToolConfig>) {
 
     // Add the appropriate cursor component depending on how the tool needs to be placed
     match config.0.tool_type {
-        PrefabToolType::Attachable(ref config) => {
+        PrefabToolType::Attachable(ref attachable) => {
             commands.entity(tool_entity).insert(AttachableCursor {
-                distance: config.distance,
-                forward: config.forward,
+                distance: attachable.distance,
+                forward: attachable.forward,
+                rotation: 0.,
+                rules: attachable.rules.clone(),
+                tool: config.0.key.clone(),
             });
         }
         PrefabToolType::Object => {
-            commands.entity(tool_entity).insert(ObjectCursor);
+            commands.entity(tool_entity).insert(ObjectCursor::default());
         }
     }
 }
@@ -96,10 +119,21 @@ fn setup_tool(mut commands: Commands, config: Res<PrefabToolConfig>) {
 fn handle_place_attachable_events(
     mut commands: Commands,
     mut click_events: EventReader<PlaceAttachableEvent>,
-    tool_query: Query<&PrefabToolCursor>,
+    tool_query: Query<(&PrefabToolCursor, &AttachableCursor)>,
+    rotation_settings: Res<RotationSettings>,
+    placement_settings: Res<PlacementSettings>,
+    brush_settings: Res<BrushSettings>,
 ) {
+    // Clicks are handled by the brush while it is enabled
+    if brush_settings.enabled {
+        click_events.clear();
+        return;
+    }
     for event in click_events.read() {
-        if let Ok(prefab_tool) = tool_query.get(event.tool) {
+        if let Ok((prefab_tool, cursor)) = tool_query.get(event.tool) {
+            if !can_place(event.valid, &prefab_tool.name, &placement_settings) {
+                continue;
+            }
             info!("[{TOOL_NAME} - {}] ==> Place attachable", prefab_tool.name);
 
             // Spawn the scene
@@ -112,33 +146,93 @@ fn handle_place_attachable_events(
                 scene_handle: prefab_tool.scene_handle.clone(),
                 transform: event.transform,
                 parent: Some(event.target),
+                anchor: Some(AttachableAnchor {
+                    distance: cursor.distance,
+                    forward: cursor.forward,
+                    spin: rotation_settings.snap(cursor.rotation),
+                }),
+                source: prefab_tool.key.clone(),
+                send_result: true,
             });
         }
     }
 }
 
+/// Utility that returns whether an object can be placed, warning about invalid placements.
+fn can_place(valid: bool, tool_name: &str, placement_settings: &PlacementSettings) -> bool {
+    if valid {
+        return true;
+    }
+    if placement_settings.reject_invalid {
+        warn!("[{TOOL_NAME} - {tool_name}] ==> Invalid placement, not placing");
+        false
+    } else {
+        warn!("[{TOOL_NAME} - {tool_name}] ==> Invalid placement");
+        true
+    }
+}
+
 /// Handle click events for physics entities
+///
+/// While the array is enabled, a copy is placed at each position in the array. Copies outside the `GardenBounds` are
+/// skipped, and a single rollback is saved for the whole array.
+///
+/// NOTE: Placements are validated with the bounds of the whole preview, so an array is invalid if any copy overlaps an
+/// existing object.
 #[allow(clippy::type_complexity)]
 fn handle_place_object_events(
     mut commands: Commands,
     mut events: EventReader<PlaceObjectEvent>,
     tool_query: Query<&PrefabToolCursor>,
+    placement_settings: Res<PlacementSettings>,
+    brush_settings: Res<BrushSettings>,
+    array_settings: Res<ArraySettings>,
+    garden_bounds: Res<GardenBounds>,
 ) {
+    // Clicks are handled by the brush while it is enabled
+    if brush_settings.enabled {
+        events.clear();
+        return;
+    }
     for event in events.read() {
         if let Ok(prefab_tool) = tool_query.get(event.tool) {
-            info!("[{TOOL_NAME} - {}] ==> Place object", prefab_tool.name);
+            if !can_place(event.valid, &prefab_tool.name, &placement_settings) {
+                continue;
+            }
+
+            let transforms: Vec<Transform> = if array_settings.enabled {
+                array_settings
+                    .transforms(&event.transform)
+                    .into_iter()
+                    .filter(|transform| garden_bounds.contains(transform.translation))
+                    .collect()
+            } else {
+                vec![event.transform]
+            };
+            info!(
+                "[{TOOL_NAME} - {}] ==> Place {} object(s)",
+                prefab_tool.name,
+                transforms.len()
+            );
 
             // Spawn the scene
             // NOTE: We use a custom command to spawn the scene because it allows us to operate on the scene before it
             // is spawned (updating positions, clearing velocities, change layers etc.).
             // Using `DynamicSceneBundle`s can cause issues because of their hierarchies -- it's much cleaner if you
             // know that all top-level objects in the game do not have a parent.
-            commands.add(SpawnPrefabCommand {
-                tool_name: prefab_tool.name.clone(),
-                scene_handle: prefab_tool.scene_handle.clone(),
-                transform: event.transform,
-                parent: None,
-            });
+            let last = transforms.len().saturating_sub(1);
+            for (index, transform) in transforms.into_iter().enumerate() {
+                commands.add(SpawnPrefabCommand {
+                    tool_name: prefab_tool.name.clone(),
+                    scene_handle: prefab_tool.scene_handle.clone(),
+                    transform,
+                    parent: None,
+                    anchor: None,
+                    source: prefab_tool.key.clone(),
+                    // NOTE: Only the last copy saves a rollback, so the whole array is undone in one step
+                    send_result: index == last,
+                });
+            }
         }
     }
 }
@@ -221,23 +315,86 @@ fn handle_tool_change_events(
     }
 }
 
-/// Scale the scene when the mouse is scrolled
+/// Scale the scene when the mouse is scrolled while holding `EditorAction::Scale`, if the tool allows scaling
 fn handle_mouse_scroll(
     mut scroll_evr: EventReader<MouseWheel>,
     mut query: Query<(&mut Transform, &PrefabToolCursor)>,
+    input: ActionInput,
+    scale_settings: Res<ScaleSettings>,
+    mut scroll_captured: ResMut<ScrollCaptured>,
 ) {
+    // Scrolling without the scale modifier zooms the camera, and scrolling while holding the rotate modifier rotates
+    // the cursor instead, see `handle_rotate_input`
+    if !input.pressed(&EditorAction::Scale) || input.pressed(&EditorAction::Rotate) {
+        scroll_evr.clear();
+        return;
+    }
+
     for event in scroll_evr.read() {
         for (mut transform, cursor) in query.iter_mut() {
             if let Some(scaling) = cursor.scaling.as_ref() {
-                let new_scale = (transform.scale.x + event.y * SCALE_SPEED)
-                    .max(scaling.min)
-                    .min(scaling.max);
+                // Stop the camera from zooming while scaling
+                scroll_captured.0 = true;
+                let new_scale =
+                    scaling.clamp(transform.scale.x + event.y * scale_settings.scroll_speed);
                 transform.scale = Vec3::splat(new_scale);
             }
         }
     }
 }
 
+/// Rotate the cursor when using the rotate controls
+fn handle_rotate_input(
+    mut rotate_input: RotateInput,
+    mut query: Query<
+        (Option<&mut ObjectCursor>, Option<&mut AttachableCursor>),
+        With<PrefabToolCursor>,
+    >,
+) {
+    let angle = rotate_input.read_angle();
+    if angle == 0. {
+        return;
+    }
+
+    for (object_cursor, attachable_cursor) in query.iter_mut() {
+        if let Some(mut cursor) = object_cursor {
+            cursor.rotation += angle;
+        }
+        if let Some(mut cursor) = attachable_cursor {
+            cursor.rotation += angle;
+        }
+    }
+}
+
+/// Tint the cursor preview while its placement is invalid
+fn update_cursor_tint(
+    mut commands: Commands,
+    query: Query<(Entity, &CursorPlacement), With<PrefabToolCursor>>,
+    children_query: Query<&Children>,
+    mesh_query: Query<Option<&TransparentMaterialTint>, With<TransparentMaterial>>,
+    placement_settings: Res<PlacementSettings>,
+) {
+    for (entity, placement) in query.iter() {
+        let tint = (!placement.is_valid())
+            .then_some(TransparentMaterialTint(placement_settings.invalid_color));
+        // NOTE: Check every frame, because the preview meshes are spawned after the scene has loaded
+        for child in children_query.iter_descendants(entity) {
+            let Ok(current) = mesh_query.get(child) else {
+                continue;
+            };
+            match tint {
+                Some(tint) if current != Some(&tint) => {
+                    commands.entity(child).insert(tint);
+                }
+                None if current.is_some() => {
+                    commands.entity(child).remove::<TransparentMaterialTint>();
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
 /// Handles `PrefabToolResult` events, which are emitted by the `` command after attempting to spawn the object into
 /// the world.
 /// When successful, it saves a rollback. If you had "toast" messages in the game, this is where you'd show any error
@@ -6,6 +6,7 @@ use editor::prelude::{PrefabConfig, ToolScaling};
 #[derive(Component)]
 pub(crate) struct PrefabToolCursor {
     pub name: String,
+    pub key: Option<String>,
     pub scene_handle: Handle<DynamicScene>,
     pub scaling: Option<ToolScaling>,
 }
@@ -5,7 +5,7 @@ use crate::utils::ensure_directory_exists_for_filename;
 #[cfg(not(target_arch = "wasm32"))]
 use {
     super::utils::*,
-    crate::events::SaveResult,
+    crate::{events::SaveResult, rollbacks::Rollbacks, unsaved_changes::UnsavedChanges},
     bevy::tasks::IoTaskPool,
     std::{fs::File, io::Write},
 };
@@ -58,6 +58,11 @@ impl Command for SaveLevelCommand {
             })
             .detach();
 
+        // the active checkpoint now matches the saved file
+        world.resource_scope(|world, mut unsaved_changes: Mut<UnsavedChanges>| {
+            unsaved_changes.mark_saved(world.resource::<Rollbacks>());
+        });
+
         // emit the success event
         // TODO: Can we listen for the `IoTaskPool` result, and include error messages?
         world.send_event(SaveResult::LevelSave(Ok(self.filename)));
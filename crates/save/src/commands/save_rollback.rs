@@ -1,6 +1,6 @@
 use bevy::{ecs::system::Command, prelude::*};
 
-use crate::{events::SaveResult, rollbacks::Rollbacks};
+use crate::{events::SaveResult, rollbacks::Rollbacks, unsaved_changes::UnsavedChanges};
 
 use super::utils::*;
 
@@ -29,6 +29,11 @@ impl Command for SaveRollbackCommand {
             rollbacks.count()
         );
 
+        // update the unsaved changes state
+        world.resource_scope(|world, mut unsaved_changes: Mut<UnsavedChanges>| {
+            unsaved_changes.checkpoint_pushed(world.resource::<Rollbacks>());
+        });
+
         // emit the success result
         world.send_event(SaveResult::RollbackSave(Ok(())));
     }
@@ -5,8 +5,11 @@ pub mod plugin;
 pub mod registry;
 pub mod rollbacks;
 pub mod types;
+pub mod unsaved_changes;
 pub mod utils;
 
 pub mod prelude {
-    pub use crate::{app::*, events::*, plugin::*, registry::*, types::*, utils::*};
+    pub use crate::{
+        app::*, events::*, plugin::*, registry::*, types::*, unsaved_changes::*, utils::*,
+    };
 }
@@ -1,6 +1,9 @@
 use bevy::{asset::LoadState, prelude::*};
 
-use super::{app::AppSaveableExt, commands::*, events::*, rollbacks::Rollbacks, types::*};
+use super::{
+    app::AppSaveableExt, commands::*, events::*, rollbacks::Rollbacks, types::*,
+    unsaved_changes::UnsavedChanges,
+};
 
 /// Plugin that adds saving and loading to an app.
 pub struct SavePlugin;
@@ -8,6 +11,7 @@ pub struct SavePlugin;
 impl Plugin for SavePlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(Rollbacks::default())
+            .init_resource::<UnsavedChanges>()
             .add_event::<RollbackSaveEvent>()
             .add_event::<LevelLoadSuccess>()
             .add_event::<LevelLoadFail>()
@@ -27,18 +31,32 @@ impl Plugin for SavePlugin {
             // saved. `WriteSceneToWorldCommand` MUST re-parent the entities after applying the scene.
             .register_saveable::<Transform>()
             // Run systems after the update set. Not sure if this is best?
+            // NOTE: New rollbacks are saved before rollbacks are applied, so a change finished in the same frame as an
+            // undo is saved first and then undone, instead of being saved on top of the undone state. Rollbacks are
+            // cleared before saving, so a new level can clear the previous level's rollbacks and save its initial
+            // rollback in the same frame.
+            .configure_sets(PostUpdate, (SaveSet::SaveRollback, SaveSet::Apply).chain())
             .add_systems(
                 PostUpdate,
                 (
+                    handle_rollback_clear_events.run_if(on_event::<RollbackClearEvent>()),
                     handle_rollback_save_events.run_if(on_event::<RollbackSaveEvent>()),
+                    apply_deferred,
+                )
+                    .chain()
+                    .in_set(SaveSet::SaveRollback),
+            )
+            .add_systems(
+                PostUpdate,
+                (
                     handle_rollback_back_events.run_if(on_event::<RollbackBackEvent>()),
                     handle_rollback_load_events.run_if(on_event::<RollbackLoadEvent>()),
                     handle_rollback_forward_events.run_if(on_event::<RollbackForwardEvent>()),
-                    handle_rollback_clear_events.run_if(on_event::<RollbackClearEvent>()),
                     handle_save_events.run_if(on_event::<SaveEvent>()),
                     handle_load_events.run_if(on_event::<LoadEvent>()),
                     handle_pending_levels.run_if(resource_exists::<PendingLevelLoad>()),
-                ),
+                )
+                    .in_set(SaveSet::Apply),
             );
     }
 }
@@ -59,11 +77,13 @@ fn handle_rollback_load_events(
     mut events: EventReader<RollbackLoadEvent>,
     query: Query<Entity, Or<(With<DespawnOnLoad>, With<Saveable>)>>,
     mut rollbacks: ResMut<Rollbacks>,
+    mut unsaved_changes: ResMut<UnsavedChanges>,
     mut success_writer: EventWriter<LevelLoadSuccess>,
     mut fail_writer: EventWriter<LevelLoadFail>,
 ) {
     for _ in events.read() {
         let result = apply_rollback(&mut commands, &query, &mut rollbacks, 0);
+        unsaved_changes.checkpoint_applied(&rollbacks);
         // TODO: Fix result event messages/path
         match result {
             Ok(_) => success_writer.send(LevelLoadSuccess("".to_string())),
@@ -85,6 +105,7 @@ fn handle_rollback_back_events(
     mut rollback_events: EventReader<RollbackBackEvent>,
     query: Query<Entity, Or<(With<DespawnOnLoad>, With<Saveable>)>>,
     mut rollbacks: ResMut<Rollbacks>,
+    mut unsaved_changes: ResMut<UnsavedChanges>,
     mut save_result_writer: EventWriter<SaveResult>,
 ) {
     for _ in rollback_events.read() {
@@ -93,6 +114,7 @@ fn handle_rollback_back_events(
             &mut commands,
             &query,
             &mut rollbacks,
+            &mut unsaved_changes,
             &mut save_result_writer,
         );
     }
@@ -105,6 +127,7 @@ fn handle_rollback_forward_events(
     mut rollback_events: EventReader<RollbackForwardEvent>,
     query: Query<Entity, Or<(With<DespawnOnLoad>, With<Saveable>)>>,
     mut rollbacks: ResMut<Rollbacks>,
+    mut unsaved_changes: ResMut<UnsavedChanges>,
     mut save_result_writer: EventWriter<SaveResult>,
 ) {
     for _ in rollback_events.read() {
@@ -113,6 +136,7 @@ fn handle_rollback_forward_events(
             &mut commands,
             &query,
             &mut rollbacks,
+            &mut unsaved_changes,
             &mut save_result_writer,
         );
     }
@@ -125,9 +149,11 @@ fn apply_rollback_event(
     commands: &mut Commands,
     query: &Query<Entity, Or<(With<DespawnOnLoad>, With<Saveable>)>>,
     rollbacks: &mut ResMut<Rollbacks>,
+    unsaved_changes: &mut ResMut<UnsavedChanges>,
     save_result_writer: &mut EventWriter<SaveResult>,
 ) {
     let result = apply_rollback(commands, query, rollbacks, direction);
+    unsaved_changes.checkpoint_applied(rollbacks);
     if result.is_ok() {
         save_result_writer.send(SaveResult::RollbackApply(Ok(())))
     } else {
@@ -142,9 +168,11 @@ fn apply_rollback_event(
 fn handle_rollback_clear_events(
     mut clear_events: EventReader<RollbackClearEvent>,
     mut rollbacks: ResMut<Rollbacks>,
+    mut unsaved_changes: ResMut<UnsavedChanges>,
 ) {
     for _ in clear_events.read() {
         rollbacks.clear_checkpoints();
+        unsaved_changes.reset();
     }
 }
 
@@ -184,6 +212,7 @@ fn handle_pending_levels(
     pending_level: Res<PendingLevelLoad>,
     asset_server: Res<AssetServer>,
     query: Query<Entity, Or<(With<DespawnOnLoad>, With<Saveable>)>>,
+    mut unsaved_changes: ResMut<UnsavedChanges>,
     mut success_events: EventWriter<LevelLoadSuccess>,
     mut fail_events: EventWriter<LevelLoadFail>,
 ) {
@@ -201,6 +230,9 @@ fn handle_pending_levels(
             };
             commands.add(cmd);
 
+            // The world now matches the loaded file
+            unsaved_changes.reset();
+
             // Send the success event, and remove the pending level load resource
             success_events.send(LevelLoadSuccess(pending_level.path.clone()));
             commands.remove_resource::<PendingLevelLoad>();
@@ -221,3 +253,37 @@ fn handle_pending_levels(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(SavePlugin)
+            .insert_resource(Assets::<DynamicScene>::default());
+        app
+    }
+
+    #[test]
+    fn test_new_level_keeps_initial_rollback() {
+        let mut app = test_app();
+
+        // Spawn a new level, which clears the rollbacks and saves the initial rollback in the same frame
+        app.world.spawn((Transform::default(), Saveable));
+        app.world.send_event(RollbackClearEvent);
+        app.world.send_event(RollbackSaveEvent);
+        app.update();
+
+        assert_eq!(app.world.resource::<Rollbacks>().count(), 1);
+        assert!(!app.world.resource::<UnsavedChanges>().is_dirty());
+
+        // Make an edit
+        app.world.spawn((Transform::default(), Saveable));
+        app.world.send_event(RollbackSaveEvent);
+        app.update();
+
+        assert_eq!(app.world.resource::<Rollbacks>().count(), 2);
+        assert!(app.world.resource::<UnsavedChanges>().is_dirty());
+    }
+}
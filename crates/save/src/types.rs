@@ -7,6 +7,16 @@ pub(crate) struct PendingLevelLoad {
     pub(crate) handle: Handle<DynamicScene>,
 }
 
+/// System sets of the `SavePlugin`, which run in `PostUpdate`.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, SystemSet)]
+pub enum SaveSet {
+    /// Clear and save rollbacks. Systems that finish a pending change when an undo or redo is requested should send
+    /// their `RollbackSaveEvent` BEFORE this set, so the change is saved before it is rolled back.
+    SaveRollback,
+    /// Apply rollbacks, and save or load levels.
+    Apply,
+}
+
 /// marker component for saveable entities
 #[derive(Component, Reflect, Default)]
 #[reflect(Component)]
@@ -0,0 +1,157 @@
+use bevy::prelude::*;
+
+use crate::rollbacks::Rollbacks;
+
+/// Resource that tracks whether the world differs from the last saved or loaded level.
+///
+/// It works by remembering which rollback checkpoint was active when the level was last saved. The world is considered
+/// dirty whenever the active checkpoint differs from that one, so undoing back to the saved state marks the level as
+/// clean again.
+#[derive(Resource, Default, Debug)]
+pub struct UnsavedChanges {
+    /// The checkpoint that was active when the level was last saved, if it still exists.
+    saved_checkpoint: Option<usize>,
+    dirty: bool,
+}
+
+impl UnsavedChanges {
+    /// Returns true if the world has changes that have not been saved to a file.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Marks the current changes as discarded, e.g. when the user confirms they want to leave the game without saving.
+    pub fn discard(&mut self) {
+        self.saved_checkpoint = None;
+        self.dirty = false;
+    }
+
+    /// Updates the state after a new checkpoint was pushed to `rollbacks`.
+    ///
+    /// The very first checkpoint is the initial state of a new level, so it is treated as clean.
+    pub(crate) fn checkpoint_pushed(&mut self, rollbacks: &Rollbacks) {
+        let Some(active) = rollbacks.active() else {
+            return;
+        };
+        if rollbacks.count() == 1 {
+            self.saved_checkpoint = Some(active);
+            self.dirty = false;
+            return;
+        }
+
+        // Pushing a checkpoint erases all forward checkpoints, which may include the saved one
+        if self.saved_checkpoint.is_some_and(|saved| saved >= active) {
+            self.saved_checkpoint = None;
+        }
+        self.dirty = true;
+    }
+
+    /// Updates the state after the active checkpoint changed due to an undo/redo.
+    pub(crate) fn checkpoint_applied(&mut self, rollbacks: &Rollbacks) {
+        self.dirty = rollbacks.active() != self.saved_checkpoint;
+    }
+
+    /// Marks the active checkpoint as saved.
+    pub(crate) fn mark_saved(&mut self, rollbacks: &Rollbacks) {
+        self.saved_checkpoint = rollbacks.active();
+        self.dirty = false;
+    }
+
+    /// Resets the state, e.g. after a level was loaded or the rollbacks were cleared.
+    ///
+    /// NOTE: Loading a level does not create a checkpoint, so there is no checkpoint that matches the loaded level.
+    /// Applying any rollback after a load will mark the world as dirty.
+    pub(crate) fn reset(&mut self) {
+        self.saved_checkpoint = None;
+        self.dirty = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Pushes a checkpoint, like `SaveRollbackCommand` does.
+    fn push(rollbacks: &mut Rollbacks, unsaved_changes: &mut UnsavedChanges) {
+        rollbacks.push_checkpoint(Handle::default());
+        unsaved_changes.checkpoint_pushed(rollbacks);
+    }
+
+    /// Applies a rollback in the given direction, like the undo/redo systems do.
+    fn apply(rollbacks: &mut Rollbacks, unsaved_changes: &mut UnsavedChanges, direction: isize) {
+        rollbacks.rollback(direction);
+        unsaved_changes.checkpoint_applied(rollbacks);
+    }
+
+    /// Returns the rollbacks and state of a new level, which has only the initial checkpoint.
+    fn new_level() -> (Rollbacks, UnsavedChanges) {
+        let mut rollbacks = Rollbacks::default();
+        let mut unsaved_changes = UnsavedChanges::default();
+        push(&mut rollbacks, &mut unsaved_changes);
+        (rollbacks, unsaved_changes)
+    }
+
+    #[test]
+    fn test_new_level_edit_is_dirty() {
+        let (mut rollbacks, mut unsaved_changes) = new_level();
+        assert!(!unsaved_changes.is_dirty());
+
+        push(&mut rollbacks, &mut unsaved_changes);
+        assert!(unsaved_changes.is_dirty());
+
+        // Undoing back to the initial checkpoint is clean again
+        apply(&mut rollbacks, &mut unsaved_changes, 1);
+        assert!(!unsaved_changes.is_dirty());
+    }
+
+    #[test]
+    fn test_undo_and_redo_across_save() {
+        let (mut rollbacks, mut unsaved_changes) = new_level();
+        push(&mut rollbacks, &mut unsaved_changes);
+        unsaved_changes.mark_saved(&rollbacks);
+        assert!(!unsaved_changes.is_dirty());
+
+        apply(&mut rollbacks, &mut unsaved_changes, 1);
+        assert!(unsaved_changes.is_dirty());
+
+        apply(&mut rollbacks, &mut unsaved_changes, -1);
+        assert!(!unsaved_changes.is_dirty());
+    }
+
+    #[test]
+    fn test_push_after_undo_erases_saved_checkpoint() {
+        let (mut rollbacks, mut unsaved_changes) = new_level();
+        push(&mut rollbacks, &mut unsaved_changes);
+        push(&mut rollbacks, &mut unsaved_changes);
+        unsaved_changes.mark_saved(&rollbacks);
+
+        // Undo, and make a different edit that replaces the saved checkpoint
+        apply(&mut rollbacks, &mut unsaved_changes, 1);
+        push(&mut rollbacks, &mut unsaved_changes);
+        assert_eq!(rollbacks.count(), 3);
+        assert!(unsaved_changes.is_dirty());
+
+        // No checkpoint matches the saved level anymore
+        apply(&mut rollbacks, &mut unsaved_changes, 1);
+        assert!(unsaved_changes.is_dirty());
+        apply(&mut rollbacks, &mut unsaved_changes, -1);
+        assert!(unsaved_changes.is_dirty());
+    }
+
+    #[test]
+    fn test_reset_after_clear() {
+        let (mut rollbacks, mut unsaved_changes) = new_level();
+        push(&mut rollbacks, &mut unsaved_changes);
+        assert!(unsaved_changes.is_dirty());
+
+        rollbacks.clear_checkpoints();
+        unsaved_changes.reset();
+        assert!(!unsaved_changes.is_dirty());
+
+        // The first checkpoint after clearing is the initial state of the level
+        push(&mut rollbacks, &mut unsaved_changes);
+        assert!(!unsaved_changes.is_dirty());
+        push(&mut rollbacks, &mut unsaved_changes);
+        assert!(unsaved_changes.is_dirty());
+    }
+}
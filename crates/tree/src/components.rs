@@ -17,6 +17,10 @@ pub struct TreeSize {
     pub canopy: Vec3,
 }
 
+/// Marker component for the meshes spawned as children of a tree, so they can be re-spawned when the tree changes.
+#[derive(Component)]
+pub(crate) struct TreeMesh;
+
 /// Bundle for spawning a tree.
 ///
 /// This represents what the scene files for the tree tools should contain.
@@ -22,7 +22,10 @@ impl Plugin for TreePlugin {
             // set up before any attachables that may be attached to them.
             // NOTE: its not important in this example, but if you are using a physics engine then it's important for
             // RigidBodies/Joints to be spawned in the correct order.
-            .add_systems(Update, setup_new_trees.in_set(SetupSet::RigidBody))
+            .add_systems(
+                Update,
+                (setup_new_trees, rebuild_changed_trees).in_set(SetupSet::RigidBody),
+            )
             // Register the types that should be included in save files.
             // NOTE: This automatically calls `register_type`.
             .register_saveable::<Tree>()
@@ -80,6 +83,48 @@ fn setup_new_trees(
     }
 }
 
+/// System that re-spawns the meshes of trees whose `TreeSize` changed, e.g. when it is edited in the inspector.
+///
+/// NOTE: Only the meshes are re-spawned, so attachables keep their position relative to the tree.
+#[allow(clippy::type_complexity)]
+fn rebuild_changed_trees(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    query: Query<(Entity, Ref<TreeSize>, Option<&Children>, Has<Disabled>), With<Tree>>,
+    mesh_query: Query<(), With<TreeMesh>>,
+    tree_resource: Res<TreeResource>,
+) {
+    for (entity, size, children, disabled) in query.iter() {
+        // New trees are handled by `setup_new_trees`
+        if !size.is_changed() || size.is_added() {
+            continue;
+        }
+        info!("[Tree] ==> Rebuild changed tree");
+
+        for child in children.into_iter().flatten() {
+            if mesh_query.contains(*child) {
+                commands.entity(*child).despawn_recursive();
+            }
+        }
+        spawn_trunk_mesh(
+            &mut commands,
+            &mut meshes,
+            &tree_resource,
+            entity,
+            &size,
+            disabled,
+        );
+        spawn_canopy_mesh(
+            &mut commands,
+            &mut meshes,
+            &tree_resource,
+            entity,
+            &size,
+            disabled,
+        );
+    }
+}
+
 /// Spawn the trunk mesh as a child of a tree
 fn spawn_trunk_mesh(
     commands: &mut Commands,
@@ -91,6 +136,7 @@ fn spawn_trunk_mesh(
 ) -> Entity {
     let mut cmds = commands.spawn((
         Name::new("Tree - Trunk Mesh"),
+        TreeMesh,
         PbrBundle {
             mesh: meshes.add(
                 shape::Box::new(
@@ -138,6 +184,7 @@ fn spawn_canopy_mesh(
 ) -> Entity {
     let mut cmds = commands.spawn((
         Name::new("Tree - Canopy Mesh"),
+        TreeMesh,
         PbrBundle {
             mesh: meshes.add(shape::Box::new(size.canopy.x, size.canopy.y, size.canopy.z).into()),
             material: tree_resource.canopy_material.clone(),
@@ -1,7 +1,13 @@
+mod bookmark_panel;
 mod failed_to_load_menu;
+mod input_loader;
+mod inspector;
+mod layer_panel;
 mod new_level;
+mod outliner;
 mod plugin;
 mod tool_loader;
 mod tool_panel;
+mod unsaved_changes_dialog;
 
 pub use plugin::*;
@@ -0,0 +1,270 @@
+use bevy::prelude::*;
+
+use bevy_helpers::generic_systems::despawn_recursive_with;
+use editor::prelude::*;
+use game_state::prelude::*;
+
+use crate::{config::FONT_SIZE_BASE, widgets::*};
+
+/// Plugin that displays a panel listing the camera bookmarks of the level.
+///
+/// Clicking a bookmark moves the camera to its viewpoint. Bookmarks are stored with their hotkeys, and can be renamed
+/// here. While renaming, the keyboard is captured until Enter confirms the new name or Escape cancels it.
+pub struct BookmarkPanelPlugin;
+
+impl Plugin for BookmarkPanelPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(PlayState::Active), setup_bookmark_panel)
+            .add_systems(
+                OnExit(GameState::Game),
+                (despawn_recursive_with::<OnBookmarkPanel>, stop_renaming),
+            )
+            .add_systems(
+                Update,
+                (
+                    handle_rename_input,
+                    handle_button_interactions,
+                    rebuild_bookmark_list,
+                )
+                    .chain()
+                    .run_if(in_state(PlayState::Active)),
+            );
+    }
+}
+
+const ROW_COLOR: Color = Color::NONE;
+
+/// Marker component for de-spawning the bookmark panel
+#[derive(Component)]
+struct OnBookmarkPanel;
+
+/// The node containing the bookmark rows
+#[derive(Component)]
+struct BookmarkList;
+
+/// Resource that exists while a bookmark is being renamed, containing the new name typed so far
+#[derive(Resource)]
+struct BookmarkRename {
+    slot: u8,
+    text: String,
+}
+
+#[derive(Component)]
+enum BookmarkButtonAction {
+    Recall(u8),
+    Rename(u8),
+}
+
+/// System that spawns the bookmark panel when entering the game
+fn setup_bookmark_panel(mut commands: Commands) {
+    commands
+        .spawn((
+            GameMarker,
+            OnBookmarkPanel,
+            PickableBlock,
+            NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Column,
+                    position_type: PositionType::Absolute,
+                    bottom: Val::Px(10.),
+                    right: Val::Px(10.),
+                    border: UiRect::all(Val::Px(1.)),
+                    padding: UiRect::all(Val::Px(10.)),
+                    ..default()
+                },
+                background_color: Color::BLACK.into(),
+                border_color: Color::WHITE.into(),
+                ..default()
+            },
+        ))
+        .with_children(|p| {
+            spawn_tool_panel_heading("Bookmarks", (), p);
+            p.spawn((
+                BookmarkList,
+                NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        ..default()
+                    },
+                    ..default()
+                },
+            ));
+        });
+}
+
+/// System that re-spawns the bookmark rows whenever bookmarks are added, removed or edited, or while renaming a
+/// bookmark.
+#[allow(clippy::too_many_arguments)]
+fn rebuild_bookmark_list(
+    mut commands: Commands,
+    list_query: Query<Entity, With<BookmarkList>>,
+    added_list_query: Query<(), Added<BookmarkList>>,
+    changed_query: Query<(), Changed<CameraBookmark>>,
+    bookmark_query: Query<&CameraBookmark>,
+    mut removed: RemovedComponents<CameraBookmark>,
+    rename: Option<Res<BookmarkRename>>,
+    mut was_renaming: Local<bool>,
+    button_style: Res<ToolButtonStyle>,
+) {
+    // NOTE: `BookmarkRename` is removed when renaming finishes, which can't be detected with `is_changed`
+    let rename_changed = rename.as_ref().is_some_and(|rename| rename.is_changed())
+        || *was_renaming != rename.is_some();
+    *was_renaming = rename.is_some();
+    let has_removed = removed.read().count() > 0;
+    if changed_query.is_empty() && !has_removed && !rename_changed && added_list_query.is_empty() {
+        return;
+    }
+
+    let mut bookmarks: Vec<&CameraBookmark> = bookmark_query.iter().collect();
+    bookmarks.sort_by_key(|bookmark| bookmark.slot);
+
+    for list in list_query.iter() {
+        commands.entity(list).despawn_descendants();
+        commands.entity(list).with_children(|p| {
+            if bookmarks.is_empty() {
+                spawn_tool_panel_text("No bookmarks stored", (), p);
+            }
+            for bookmark in bookmarks.iter() {
+                let name = match rename
+                    .as_ref()
+                    .filter(|rename| rename.slot == bookmark.slot)
+                {
+                    Some(rename) => format!("{}_", rename.text),
+                    None => bookmark.name.clone(),
+                };
+                spawn_row(
+                    bookmark.slot,
+                    format!("{}: {name}", bookmark.slot),
+                    &button_style,
+                    p,
+                );
+            }
+        });
+    }
+}
+
+/// Utility that spawns a row for a bookmark, with its name and a button to rename it.
+fn spawn_row(
+    slot: u8,
+    label: String,
+    button_style: &ToolButtonStyle,
+    child_builder: &mut ChildBuilder,
+) {
+    child_builder
+        .spawn(NodeBundle {
+            style: Style {
+                align_items: AlignItems::Center,
+                column_gap: Val::Px(5.),
+                margin: UiRect::bottom(Val::Px(5.)),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|p| {
+            p.spawn((
+                BookmarkButtonAction::Recall(slot),
+                ButtonBundle {
+                    style: Style {
+                        flex_grow: 1.,
+                        min_width: Val::Px(100.),
+                        padding: UiRect::all(Val::Px(5.)),
+                        ..default()
+                    },
+                    background_color: ROW_COLOR.into(),
+                    ..default()
+                },
+            ))
+            .with_children(|p| {
+                p.spawn(TextBundle::from_section(
+                    label,
+                    TextStyle {
+                        font_size: FONT_SIZE_BASE,
+                        ..default()
+                    },
+                ));
+            });
+            spawn_tool_button(
+                "Rename",
+                BookmarkButtonAction::Rename(slot),
+                button_style,
+                true,
+                p,
+            );
+        });
+}
+
+/// System that handles click events for buttons in the bookmark panel
+fn handle_button_interactions(
+    mut commands: Commands,
+    query: Query<(&BookmarkButtonAction, &Interaction), Changed<Interaction>>,
+    bookmark_query: Query<&CameraBookmark>,
+    mut keyboard_captured: ResMut<KeyboardCaptured>,
+    mut camera_writer: EventWriter<CameraEvent>,
+) {
+    for (action, interaction) in query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match action {
+            BookmarkButtonAction::Recall(slot) => {
+                camera_writer.send(CameraEvent::RecallBookmark(*slot))
+            }
+            BookmarkButtonAction::Rename(slot) => {
+                // NOTE: Only one widget can read the keyboard at a time, e.g. while a layer is being renamed
+                if keyboard_captured.0 {
+                    continue;
+                }
+                if let Some(current) = bookmark_query.iter().find(|b| b.slot == *slot) {
+                    commands.insert_resource(BookmarkRename {
+                        slot: *slot,
+                        text: current.name.clone(),
+                    });
+                    keyboard_captured.0 = true;
+                }
+            }
+        }
+    }
+}
+
+/// System that reads typed characters while renaming a bookmark. Enter renames the bookmark, and Escape cancels
+/// renaming.
+fn handle_rename_input(
+    mut commands: Commands,
+    rename: Option<ResMut<BookmarkRename>>,
+    mut characters: EventReader<ReceivedCharacter>,
+    keys: Res<Input<KeyCode>>,
+    mut keyboard_captured: ResMut<KeyboardCaptured>,
+    mut camera_writer: EventWriter<CameraEvent>,
+) {
+    let Some(mut rename) = rename else {
+        characters.clear();
+        return;
+    };
+
+    // NOTE: Control characters, such as backspace, are handled using their keys instead
+    for event in characters.read() {
+        if !event.char.is_control() {
+            rename.text.push(event.char);
+        }
+    }
+    if keys.just_pressed(KeyCode::Back) {
+        rename.text.pop();
+    }
+
+    if keys.just_pressed(KeyCode::Return) {
+        camera_writer.send(CameraEvent::RenameBookmark {
+            slot: rename.slot,
+            name: rename.text.clone(),
+        });
+    } else if !keys.just_pressed(KeyCode::Escape) {
+        return;
+    }
+    commands.remove_resource::<BookmarkRename>();
+    keyboard_captured.0 = false;
+}
+
+/// System that stops renaming when leaving the game, so the keyboard is no longer captured.
+fn stop_renaming(mut commands: Commands, mut keyboard_captured: ResMut<KeyboardCaptured>) {
+    commands.remove_resource::<BookmarkRename>();
+    keyboard_captured.0 = false;
+}
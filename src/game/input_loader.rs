@@ -0,0 +1,39 @@
+use bevy::prelude::*;
+use bevy_asset_loader::prelude::*;
+
+use editor::prelude::*;
+use game_state::prelude::*;
+
+/// Plugin which loads the key bindings and applies them to the `InputMap`
+pub struct InputLoaderPlugin;
+
+impl Plugin for InputLoaderPlugin {
+    fn build(&self, app: &mut App) {
+        app.configure_loading_state(
+            LoadingStateConfig::new(PlayState::LoadAssets).load_collection::<InputMapAssets>(),
+        )
+        .add_systems(
+            Update,
+            handle_input_map_assets.run_if(resource_added::<InputMapAssets>()),
+        );
+    }
+}
+
+/// Asset collection which loads the key bindings file.
+#[derive(AssetCollection, Resource)]
+pub(crate) struct InputMapAssets {
+    #[asset(path = "config/editor.input.ron")]
+    pub input_map: Handle<InputMapAsset>,
+}
+
+/// System that applies the key bindings to the `InputMap` once the asset collection has loaded.
+fn handle_input_map_assets(
+    input_map_assets: Res<InputMapAssets>,
+    assets: Res<Assets<InputMapAsset>>,
+    mut input_map: ResMut<InputMap>,
+) {
+    if let Some(asset) = assets.get(&input_map_assets.input_map) {
+        info!("[Input Loader] ==> applying key bindings");
+        input_map.apply_asset(asset);
+    }
+}
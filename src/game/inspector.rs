@@ -0,0 +1,498 @@
+use bevy::{
+    ecs::system::{Command, SystemState},
+    prelude::*,
+    reflect::{ReflectMut, ReflectRef},
+};
+
+use bevy_helpers::generic_systems::despawn_recursive_with;
+use editor::prelude::*;
+use game_effects::selected::Selection;
+use game_state::prelude::*;
+use save::prelude::*;
+
+use crate::{config::FONT_SIZE_BASE, widgets::*};
+
+/// Plugin that displays an inspector panel for the selected object.
+///
+/// The inspector lists the reflected, saveable components of the selected object and lets you edit their numbers,
+/// `Vec3`s, colors and bools. Each edit is applied to the component directly and saves a rollback, so it can be undone
+/// in one step. Plugins are responsible for rebuilding their objects when their components change.
+///
+/// The inspector is only shown while a single object is selected. The fields of `Locked` or `StaticLocked` objects, and
+/// of objects on a locked layer, are shown read-only.
+pub struct InspectorPlugin;
+
+impl Plugin for InspectorPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnExit(GameState::Game),
+            despawn_recursive_with::<OnInspector>,
+        )
+        .add_systems(
+            Update,
+            (
+                rebuild_inspector.run_if(resource_changed::<Selection>()),
+                handle_button_interactions,
+                update_value_texts,
+            )
+                .chain()
+                .run_if(in_state(PlayState::Active)),
+        );
+    }
+}
+
+/// Smallest amount numbers are changed by per click. Larger numbers are changed by a tenth of their order of
+/// magnitude, e.g. by 1000 for a light brightness of 32000, see `number_step`.
+const NUMBER_STEP: f32 = 0.1;
+
+/// Fields whose name contains one of these can't be negative, e.g. sizes, scales and light brightness
+const NON_NEGATIVE_FIELDS: [&str; 3] = ["scale", "size", "light"];
+
+/// Amount color channels are changed by per click
+const COLOR_STEP: f32 = 0.05;
+
+/// Steps are multiplied by this amount while holding shift
+const LARGE_STEP_MULTIPLIER: f32 = 10.;
+
+/// Marker component for de-spawning the inspector
+#[derive(Component)]
+struct OnInspector;
+
+/// A single editable value in a component on the inspected entity.
+#[derive(Debug, Clone, PartialEq)]
+struct InspectorField {
+    entity: Entity,
+    /// Type path of the component
+    component: String,
+    /// Name of the field in the component
+    field: String,
+    kind: FieldKind,
+    /// Whether the value is clamped to zero when decreased, e.g. for sizes
+    non_negative: bool,
+}
+
+/// The type of an editable field, and which part of it is edited.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FieldKind {
+    Number,
+    Bool,
+    /// An axis of a `Vec3`
+    Vec3Axis(usize),
+    /// A channel of a `Color`, in the order red, green, blue
+    ColorChannel(usize),
+}
+
+impl FieldKind {
+    /// Returns the kinds used to edit a field value, or an empty list if the value can't be edited.
+    fn from_value(value: &dyn Reflect) -> Vec<FieldKind> {
+        if value.is::<f32>() {
+            vec![FieldKind::Number]
+        } else if value.is::<bool>() {
+            vec![FieldKind::Bool]
+        } else if value.is::<Vec3>() {
+            (0..3).map(FieldKind::Vec3Axis).collect()
+        } else if value.is::<Color>() {
+            (0..3).map(FieldKind::ColorChannel).collect()
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Returns the label suffix for the part of the value that is edited.
+    fn suffix(&self) -> &'static str {
+        match self {
+            FieldKind::Number | FieldKind::Bool => "",
+            FieldKind::Vec3Axis(axis) => [".x", ".y", ".z"][*axis],
+            FieldKind::ColorChannel(channel) => [".r", ".g", ".b"][*channel],
+        }
+    }
+
+    /// Formats a field value for display.
+    fn format(&self, value: &dyn Reflect) -> Option<String> {
+        match self {
+            FieldKind::Number => value.downcast_ref::<f32>().map(|v| format!("{v:.2}")),
+            FieldKind::Bool => value.downcast_ref::<bool>().map(|v| v.to_string()),
+            FieldKind::Vec3Axis(axis) => value
+                .downcast_ref::<Vec3>()
+                .map(|v| format!("{:.2}", v[*axis])),
+            FieldKind::ColorChannel(channel) => value
+                .downcast_ref::<Color>()
+                .map(|color| format!("{:.2}", color.as_rgba_f32()[*channel])),
+        }
+    }
+
+    /// Edits a field value. Numbers are changed by `steps` times their step, and bools are toggled.
+    ///
+    /// * `non_negative` - Whether numbers are clamped to zero.
+    ///
+    /// Returns false if the value is not of the expected type.
+    fn apply(&self, value: &mut dyn Reflect, steps: f32, non_negative: bool) -> bool {
+        let nudge = |v: f32| {
+            let v = v + number_step(v) * steps;
+            if non_negative {
+                v.max(0.)
+            } else {
+                v
+            }
+        };
+        match self {
+            FieldKind::Number => value
+                .downcast_mut::<f32>()
+                .map(|v| *v = nudge(*v))
+                .is_some(),
+            FieldKind::Bool => value.downcast_mut::<bool>().map(|v| *v = !*v).is_some(),
+            FieldKind::Vec3Axis(axis) => value
+                .downcast_mut::<Vec3>()
+                .map(|v| v[*axis] = nudge(v[*axis]))
+                .is_some(),
+            FieldKind::ColorChannel(channel) => value
+                .downcast_mut::<Color>()
+                .map(|color| {
+                    let mut rgba = color.as_rgba_f32();
+                    rgba[*channel] = (rgba[*channel] + COLOR_STEP * steps).clamp(0., 1.);
+                    *color = Color::rgba(rgba[0], rgba[1], rgba[2], rgba[3]);
+                })
+                .is_some(),
+        }
+    }
+}
+
+/// A button in the inspector
+#[derive(Component)]
+enum InspectorButtonAction {
+    Decrease(InspectorField),
+    Increase(InspectorField),
+    Toggle(InspectorField),
+}
+
+/// Text displaying the current value of a field
+#[derive(Component)]
+struct InspectorValueText(InspectorField);
+
+/// Command that edits a field on the inspected entity, and saves a rollback.
+///
+/// * `steps` - Number of steps to change numbers by, negative to decrease them. It is ignored for bools, which are
+///   toggled.
+struct EditInspectorField {
+    field: InspectorField,
+    steps: f32,
+}
+
+impl Command for EditInspectorField {
+    fn apply(self, world: &mut World) {
+        // NOTE: The object may have been locked after the inspector was built
+        let mut layers_state = SystemState::<Layers>::new(world);
+        if is_edit_locked(world, &layers_state.get(world), self.field.entity) {
+            info!("[Inspector] ==> can't edit locked object");
+            return;
+        }
+
+        let Some(reflect_component) = get_reflect_component(world, &self.field.component) else {
+            return;
+        };
+        let Some(mut entity_mut) = world.get_entity_mut(self.field.entity) else {
+            return;
+        };
+        let Some(mut component) = reflect_component.reflect_mut(&mut entity_mut) else {
+            return;
+        };
+        let ReflectMut::Struct(component_struct) = component.reflect_mut() else {
+            return;
+        };
+        let Some(value) = component_struct.field_mut(&self.field.field) else {
+            return;
+        };
+        if !self
+            .field
+            .kind
+            .apply(value, self.steps, self.field.non_negative)
+        {
+            return;
+        }
+
+        info!(
+            "[Inspector] ==> edited {}.{}{}",
+            self.field.component,
+            self.field.field,
+            self.field.kind.suffix()
+        );
+        world.send_event(ObjectChanged::ComponentEdited {
+            entity: self.field.entity,
+            component: self.field.component,
+        });
+        world.send_event(RollbackSaveEvent);
+    }
+}
+
+/// System that re-spawns the inspector panel when the selection changes
+fn rebuild_inspector(
+    mut commands: Commands,
+    world: &World,
+    panel_query: Query<Entity, With<OnInspector>>,
+    selection: Res<Selection>,
+    layers: Layers,
+    button_style: Res<ToolButtonStyle>,
+) {
+    for entity in panel_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    let &[entity] = selection.entities() else {
+        return;
+    };
+    let components = get_inspector_fields(world, entity);
+    if components.is_empty() {
+        return;
+    }
+    let editable = !is_edit_locked(world, &layers, entity);
+
+    commands
+        .spawn((
+            GameMarker,
+            OnInspector,
+            PickableBlock,
+            NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Column,
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(10.),
+                    right: Val::Px(10.),
+                    width: Val::Px(260.),
+                    border: UiRect::all(Val::Px(1.)),
+                    padding: UiRect::all(Val::Px(10.)),
+                    ..default()
+                },
+                background_color: Color::BLACK.into(),
+                border_color: Color::WHITE.into(),
+                ..default()
+            },
+        ))
+        .with_children(|p| {
+            spawn_tool_panel_heading("Inspector", (), p);
+            if editable {
+                spawn_tool_panel_text("Hold shift for larger steps", (), p);
+            } else {
+                spawn_tool_panel_text("Locked objects can't be edited", (), p);
+            }
+            for (name, fields) in components.iter() {
+                spawn_tool_panel_heading(name, (), p);
+                for field in fields.iter() {
+                    let value = read_field(world, field).unwrap_or_default();
+                    spawn_field_row(field, value, editable, &button_style, p);
+                }
+            }
+        });
+}
+
+/// Utility that spawns a row with a label, the value and buttons to edit it. The buttons are left out when the field
+/// is not `editable`.
+fn spawn_field_row(
+    field: &InspectorField,
+    value: String,
+    editable: bool,
+    button_style: &ToolButtonStyle,
+    child_builder: &mut ChildBuilder,
+) {
+    let text_style = TextStyle {
+        font_size: FONT_SIZE_BASE,
+        ..default()
+    };
+
+    child_builder
+        .spawn(NodeBundle {
+            style: Style {
+                align_items: AlignItems::Center,
+                column_gap: Val::Px(5.),
+                margin: UiRect::bottom(Val::Px(5.)),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|p| {
+            p.spawn(
+                TextBundle::from_section(
+                    format!("{}{}", field.field, field.kind.suffix()),
+                    text_style.clone(),
+                )
+                .with_style(Style {
+                    flex_grow: 1.,
+                    ..default()
+                }),
+            );
+            if !editable {
+                p.spawn((
+                    InspectorValueText(field.clone()),
+                    TextBundle::from_section(value, text_style),
+                ));
+            } else if field.kind == FieldKind::Bool {
+                spawn_tool_button(
+                    "Toggle",
+                    InspectorButtonAction::Toggle(field.clone()),
+                    button_style,
+                    true,
+                    p,
+                );
+                p.spawn((
+                    InspectorValueText(field.clone()),
+                    TextBundle::from_section(value, text_style),
+                ));
+            } else {
+                spawn_tool_button(
+                    "-",
+                    InspectorButtonAction::Decrease(field.clone()),
+                    button_style,
+                    true,
+                    p,
+                );
+                p.spawn((
+                    InspectorValueText(field.clone()),
+                    TextBundle::from_section(value, text_style),
+                ));
+                spawn_tool_button(
+                    "+",
+                    InspectorButtonAction::Increase(field.clone()),
+                    button_style,
+                    true,
+                    p,
+                );
+            }
+        });
+}
+
+/// System that handles click events for buttons in the inspector
+fn handle_button_interactions(
+    mut commands: Commands,
+    query: Query<(&InspectorButtonAction, &Interaction), Changed<Interaction>>,
+    input: ActionInput,
+) {
+    let multiplier = if input.pressed(&EditorAction::LargeStep) {
+        LARGE_STEP_MULTIPLIER
+    } else {
+        1.
+    };
+
+    for (action, interaction) in query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let (field, steps) = match action {
+            InspectorButtonAction::Decrease(field) => (field, -multiplier),
+            InspectorButtonAction::Increase(field) => (field, multiplier),
+            InspectorButtonAction::Toggle(field) => (field, 0.),
+        };
+        commands.add(EditInspectorField {
+            field: field.clone(),
+            steps,
+        });
+    }
+}
+
+/// System that updates the displayed values, so they stay up to date when components are changed elsewhere, e.g. by
+/// moving the object or by an edit in the inspector.
+fn update_value_texts(world: &mut World) {
+    let mut query = world.query::<(Entity, &InspectorValueText)>();
+    let values: Vec<(Entity, String)> = query
+        .iter(world)
+        .filter_map(|(entity, value_text)| Some((entity, read_field(world, &value_text.0)?)))
+        .collect();
+
+    for (entity, value) in values {
+        let Some(mut text) = world.get_mut::<Text>(entity) else {
+            continue;
+        };
+        // NOTE: Only update the text when it changed, to avoid triggering change detection every frame
+        if text.sections[0].value != value {
+            text.sections[0].value = value;
+        }
+    }
+}
+
+/// Utility that returns the editable fields of all saveable components on an entity, grouped by component name.
+fn get_inspector_fields(world: &World, entity: Entity) -> Vec<(String, Vec<InspectorField>)> {
+    let Some(entity_ref) = world.get_entity(entity) else {
+        return Vec::new();
+    };
+    let saveable_registry = world.resource::<SaveableRegistry>();
+    let type_registry = world.resource::<AppTypeRegistry>().read();
+
+    let mut components = Vec::new();
+    for type_path in saveable_registry.types() {
+        let Some(registration) = type_registry.get_with_type_path(type_path) else {
+            continue;
+        };
+        let Some(component) = registration
+            .data::<ReflectComponent>()
+            .and_then(|reflect_component| reflect_component.reflect(entity_ref))
+        else {
+            continue;
+        };
+        let ReflectRef::Struct(component_struct) = component.reflect_ref() else {
+            continue;
+        };
+
+        let mut fields = Vec::new();
+        for (index, value) in component_struct.iter_fields().enumerate() {
+            let Some(name) = component_struct.name_at(index) else {
+                continue;
+            };
+            // NOTE: All fields of size components, such as `TreeSize`, are sizes
+            let non_negative = type_path.ends_with("Size")
+                || NON_NEGATIVE_FIELDS
+                    .iter()
+                    .any(|pattern| name.contains(pattern));
+            for kind in FieldKind::from_value(value) {
+                fields.push(InspectorField {
+                    entity,
+                    component: type_path.clone(),
+                    field: name.to_string(),
+                    kind,
+                    non_negative,
+                });
+            }
+        }
+        if !fields.is_empty() {
+            let name = registration.type_info().type_path_table().short_path();
+            components.push((name.to_string(), fields));
+        }
+    }
+
+    // NOTE: The saveable registry is not ordered, so sort the components to keep the panel stable
+    components.sort_by(|(a, _), (b, _)| a.cmp(b));
+    components
+}
+
+/// Utility that returns the amount to change a number by per click, which is a tenth of its order of magnitude so that
+/// large and small numbers are equally easy to edit. It is never smaller than `NUMBER_STEP`.
+fn number_step(value: f32) -> f32 {
+    10_f32
+        .powf(value.abs().log10().floor() - 1.)
+        .max(NUMBER_STEP)
+}
+
+/// Utility that returns true if an object can't be edited, because it is `Locked` or `StaticLocked`, or it is on a
+/// locked layer.
+fn is_edit_locked(world: &World, layers: &Layers, entity: Entity) -> bool {
+    world.get_entity(entity).is_some_and(|entity_ref| {
+        entity_ref.contains::<Locked>() || entity_ref.contains::<StaticLocked>()
+    }) || layers.of_object(entity).is_some_and(|layer| layer.locked)
+}
+
+/// Utility that reads the formatted value of a field.
+fn read_field(world: &World, field: &InspectorField) -> Option<String> {
+    let reflect_component = get_reflect_component(world, &field.component)?;
+    let component = reflect_component.reflect(world.get_entity(field.entity)?)?;
+    let ReflectRef::Struct(component_struct) = component.reflect_ref() else {
+        return None;
+    };
+    field.kind.format(component_struct.field(&field.field)?)
+}
+
+/// Utility that returns the `ReflectComponent` for a component type path.
+fn get_reflect_component(world: &World, type_path: &str) -> Option<ReflectComponent> {
+    world
+        .resource::<AppTypeRegistry>()
+        .read()
+        .get_with_type_path(type_path)?
+        .data::<ReflectComponent>()
+        .cloned()
+}
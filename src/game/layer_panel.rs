@@ -0,0 +1,339 @@
+use bevy::prelude::*;
+
+use bevy_helpers::generic_systems::despawn_recursive_with;
+use editor::prelude::*;
+use game_effects::selected::Selection;
+use game_state::prelude::*;
+
+use crate::{config::FONT_SIZE_BASE, widgets::*};
+
+/// Plugin that displays a panel listing the layers of the level.
+///
+/// Clicking a layer makes it the active layer, which new objects are placed on. Each layer can be shown or hidden,
+/// locked, moved up and down, and renamed. While renaming, the keyboard is captured until Enter confirms the new name
+/// or Escape cancels it.
+pub struct LayerPanelPlugin;
+
+impl Plugin for LayerPanelPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(PlayState::Active), setup_layer_panel)
+            .add_systems(
+                OnExit(GameState::Game),
+                (despawn_recursive_with::<OnLayerPanel>, stop_renaming),
+            )
+            .add_systems(
+                Update,
+                (
+                    handle_rename_input,
+                    handle_button_interactions,
+                    rebuild_layer_list,
+                )
+                    .chain()
+                    .run_if(in_state(PlayState::Active)),
+            );
+    }
+}
+
+const ROW_COLOR: Color = Color::NONE;
+const ROW_ACTIVE_COLOR: Color = Color::GRAY;
+
+/// Marker component for de-spawning the layer panel
+#[derive(Component)]
+struct OnLayerPanel;
+
+/// The node containing the layer rows
+#[derive(Component)]
+struct LayerList;
+
+/// Resource that exists while a layer is being renamed, containing the new name typed so far
+#[derive(Resource)]
+struct LayerRename {
+    layer: u32,
+    text: String,
+}
+
+#[derive(Component)]
+enum LayerButtonAction {
+    Create,
+    /// Move the selected objects to the active layer
+    AssignSelection,
+    SetActive(u32),
+    ToggleVisible(u32),
+    ToggleLocked(u32),
+    MoveUp(u32),
+    MoveDown(u32),
+    Rename(u32),
+}
+
+/// System that spawns the layer panel when entering the game
+fn setup_layer_panel(mut commands: Commands, button_style: Res<ToolButtonStyle>) {
+    commands
+        .spawn((
+            GameMarker,
+            OnLayerPanel,
+            PickableBlock,
+            NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Column,
+                    position_type: PositionType::Absolute,
+                    // NOTE: Positioned underneath the outliner
+                    bottom: Val::Px(10.),
+                    left: Val::Px(230.),
+                    border: UiRect::all(Val::Px(1.)),
+                    padding: UiRect::all(Val::Px(10.)),
+                    ..default()
+                },
+                background_color: Color::BLACK.into(),
+                border_color: Color::WHITE.into(),
+                ..default()
+            },
+        ))
+        .with_children(|p| {
+            spawn_tool_panel_heading("Layers", (), p);
+            p.spawn((
+                LayerList,
+                NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        ..default()
+                    },
+                    ..default()
+                },
+            ));
+            spawn_tool_button(
+                "New Layer",
+                LayerButtonAction::Create,
+                &button_style,
+                false,
+                p,
+            );
+            spawn_tool_button(
+                "Move Selection to Active Layer",
+                LayerButtonAction::AssignSelection,
+                &button_style,
+                true,
+                p,
+            );
+        });
+}
+
+/// System that re-spawns the layer rows whenever layers are added, removed or edited, the active layer changes, or
+/// while renaming a layer.
+#[allow(clippy::too_many_arguments)]
+fn rebuild_layer_list(
+    mut commands: Commands,
+    list_query: Query<Entity, With<LayerList>>,
+    added_list_query: Query<(), Added<LayerList>>,
+    changed_query: Query<(), Changed<Layer>>,
+    mut removed: RemovedComponents<Layer>,
+    layers: Layers,
+    active_layer: Res<ActiveLayer>,
+    rename: Option<Res<LayerRename>>,
+    mut was_renaming: Local<bool>,
+    button_style: Res<ToolButtonStyle>,
+) {
+    // NOTE: `LayerRename` is removed when renaming finishes, which can't be detected with `is_changed`
+    let rename_changed = rename.as_ref().is_some_and(|rename| rename.is_changed())
+        || *was_renaming != rename.is_some();
+    *was_renaming = rename.is_some();
+    let has_removed = removed.read().count() > 0;
+    if changed_query.is_empty()
+        && !has_removed
+        && !active_layer.is_changed()
+        && !rename_changed
+        && added_list_query.is_empty()
+    {
+        return;
+    }
+
+    for list in list_query.iter() {
+        commands.entity(list).despawn_descendants();
+        commands.entity(list).with_children(|p| {
+            for (_, layer) in layers.sorted() {
+                let label = match rename.as_ref().filter(|rename| rename.layer == layer.id) {
+                    Some(rename) => format!("{}_", rename.text),
+                    None => layer.name.clone(),
+                };
+                spawn_row(layer, label, layer.id == active_layer.0, &button_style, p);
+            }
+        });
+    }
+}
+
+/// Utility that spawns a row for a layer, with its name and buttons to edit it.
+fn spawn_row(
+    layer: &Layer,
+    label: String,
+    is_active: bool,
+    button_style: &ToolButtonStyle,
+    child_builder: &mut ChildBuilder,
+) {
+    child_builder
+        .spawn(NodeBundle {
+            style: Style {
+                align_items: AlignItems::Center,
+                column_gap: Val::Px(5.),
+                margin: UiRect::bottom(Val::Px(5.)),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|p| {
+            p.spawn((
+                LayerButtonAction::SetActive(layer.id),
+                ButtonBundle {
+                    style: Style {
+                        flex_grow: 1.,
+                        min_width: Val::Px(100.),
+                        padding: UiRect::all(Val::Px(5.)),
+                        ..default()
+                    },
+                    background_color: if is_active {
+                        ROW_ACTIVE_COLOR
+                    } else {
+                        ROW_COLOR
+                    }
+                    .into(),
+                    ..default()
+                },
+            ))
+            .with_children(|p| {
+                p.spawn(TextBundle::from_section(
+                    label,
+                    TextStyle {
+                        font_size: FONT_SIZE_BASE,
+                        ..default()
+                    },
+                ));
+            });
+            spawn_tool_button(
+                if layer.visible { "Hide" } else { "Show" },
+                LayerButtonAction::ToggleVisible(layer.id),
+                button_style,
+                true,
+                p,
+            );
+            spawn_tool_button(
+                if layer.locked { "Unlock" } else { "Lock" },
+                LayerButtonAction::ToggleLocked(layer.id),
+                button_style,
+                true,
+                p,
+            );
+            spawn_tool_button(
+                "Up",
+                LayerButtonAction::MoveUp(layer.id),
+                button_style,
+                true,
+                p,
+            );
+            spawn_tool_button(
+                "Down",
+                LayerButtonAction::MoveDown(layer.id),
+                button_style,
+                true,
+                p,
+            );
+            spawn_tool_button(
+                "Rename",
+                LayerButtonAction::Rename(layer.id),
+                button_style,
+                true,
+                p,
+            );
+        });
+}
+
+/// System that handles click events for buttons in the layer panel
+fn handle_button_interactions(
+    mut commands: Commands,
+    query: Query<(&LayerButtonAction, &Interaction), Changed<Interaction>>,
+    layers: Layers,
+    active_layer: Res<ActiveLayer>,
+    selection: Res<Selection>,
+    mut keyboard_captured: ResMut<KeyboardCaptured>,
+    mut layer_writer: EventWriter<LayerEvent>,
+) {
+    for (action, interaction) in query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match action {
+            LayerButtonAction::Create => layer_writer.send(LayerEvent::Create),
+            LayerButtonAction::AssignSelection => layer_writer.send(LayerEvent::AssignObjects {
+                layer: active_layer.0,
+                entities: selection.entities().to_vec(),
+            }),
+            LayerButtonAction::SetActive(layer) => layer_writer.send(LayerEvent::SetActive(*layer)),
+            LayerButtonAction::ToggleVisible(layer) => {
+                layer_writer.send(LayerEvent::ToggleVisible(*layer))
+            }
+            LayerButtonAction::ToggleLocked(layer) => {
+                layer_writer.send(LayerEvent::ToggleLocked(*layer))
+            }
+            LayerButtonAction::MoveUp(layer) => layer_writer.send(LayerEvent::Reorder {
+                layer: *layer,
+                offset: -1,
+            }),
+            LayerButtonAction::MoveDown(layer) => layer_writer.send(LayerEvent::Reorder {
+                layer: *layer,
+                offset: 1,
+            }),
+            LayerButtonAction::Rename(layer) => {
+                // NOTE: Only one widget can read the keyboard at a time, e.g. while a bookmark is being renamed
+                if keyboard_captured.0 {
+                    continue;
+                }
+                if let Some((_, current)) = layers.get(*layer) {
+                    commands.insert_resource(LayerRename {
+                        layer: *layer,
+                        text: current.name.clone(),
+                    });
+                    keyboard_captured.0 = true;
+                }
+            }
+        }
+    }
+}
+
+/// System that reads typed characters while renaming a layer. Enter renames the layer, and Escape cancels renaming.
+fn handle_rename_input(
+    mut commands: Commands,
+    rename: Option<ResMut<LayerRename>>,
+    mut characters: EventReader<ReceivedCharacter>,
+    keys: Res<Input<KeyCode>>,
+    mut keyboard_captured: ResMut<KeyboardCaptured>,
+    mut layer_writer: EventWriter<LayerEvent>,
+) {
+    let Some(mut rename) = rename else {
+        characters.clear();
+        return;
+    };
+
+    // NOTE: Control characters, such as backspace, are handled using their keys instead
+    for event in characters.read() {
+        if !event.char.is_control() {
+            rename.text.push(event.char);
+        }
+    }
+    if keys.just_pressed(KeyCode::Back) {
+        rename.text.pop();
+    }
+
+    if keys.just_pressed(KeyCode::Return) {
+        layer_writer.send(LayerEvent::Rename {
+            layer: rename.layer,
+            name: rename.text.clone(),
+        });
+    } else if !keys.just_pressed(KeyCode::Escape) {
+        return;
+    }
+    commands.remove_resource::<LayerRename>();
+    keyboard_captured.0 = false;
+}
+
+/// System that stops renaming when leaving the game, so the keyboard is no longer captured.
+fn stop_renaming(mut commands: Commands, mut keyboard_captured: ResMut<KeyboardCaptured>) {
+    commands.remove_resource::<LayerRename>();
+    keyboard_captured.0 = false;
+}
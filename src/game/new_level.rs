@@ -21,6 +21,7 @@ impl Plugin for NewLevelPlugin {
 fn spawn_new_level(
     mut commands: Commands,
     mut next_state: ResMut<NextState<PlayState>>,
+    mut rollback_clear_writer: EventWriter<RollbackClearEvent>,
     mut rollback_save_writer: EventWriter<RollbackSaveEvent>,
 ) {
     // Spawn a background
@@ -36,12 +37,18 @@ fn spawn_new_level(
         despawn_on_load: DespawnOnLoad,
     });
 
+    // Spawn the default layer, and place new objects on it
+    commands.spawn((Layer::default(), GameMarker, Saveable, DespawnOnLoad));
+    commands.insert_resource(ActiveLayer::default());
+
     // Spawn a tree
     commands.spawn(TreeBundle::new(Transform::from_translation(Vec3::ZERO)));
 
     // Transition to the next state
     next_state.set(PlayState::SetupLevel);
 
-    // Save the initial rollback
+    // Clear the rollbacks from any previous level, and save the initial rollback. The initial rollback is treated as
+    // having no unsaved changes.
+    rollback_clear_writer.send(RollbackClearEvent);
     rollback_save_writer.send(RollbackSaveEvent);
 }
@@ -0,0 +1,308 @@
+use bevy::{input::mouse::MouseWheel, prelude::*, ui::RelativeCursorPosition};
+
+use bevy_helpers::generic_systems::despawn_recursive_with;
+use editor::prelude::*;
+use game_effects::selected::{SelectEvent, Selection};
+use game_state::prelude::*;
+use save::prelude::Saveable;
+
+use crate::{config::FONT_SIZE_BASE, widgets::*};
+
+/// Plugin that displays an outliner panel listing every object in the garden.
+///
+/// Top-level objects are listed by their `Name`, with attachables nested underneath the object they are attached to.
+/// Clicking a row selects the object, and holding `EditorAction::ToggleSelection` while clicking adds or removes it
+/// from the selection. The list is rebuilt whenever objects are added, removed, renamed or re-parented.
+///
+/// Saveable entities that only store level data, such as layers and camera bookmarks, are not listed.
+pub struct OutlinerPlugin;
+
+impl Plugin for OutlinerPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(PlayState::Active), setup_outliner)
+            .add_systems(
+                OnExit(GameState::Game),
+                despawn_recursive_with::<OnOutliner>,
+            )
+            .add_systems(
+                Update,
+                (
+                    rebuild_outliner_list,
+                    update_row_styles,
+                    handle_row_interactions,
+                    handle_mouse_scroll,
+                )
+                    .chain()
+                    .run_if(in_state(PlayState::Active)),
+            );
+    }
+}
+
+/// Maximum height of the outliner panel, as a percentage of the window height
+const MAX_HEIGHT_PERCENT: f32 = 60.;
+
+/// Amount of pixels to scroll per line of mouse scroll
+const SCROLL_SPEED: f32 = 20.;
+
+/// Indentation in pixels for each level of nesting
+const INDENT: f32 = 12.;
+
+const ROW_COLOR: Color = Color::NONE;
+const ROW_HOVER_COLOR: Color = Color::DARK_GRAY;
+const ROW_SELECTED_COLOR: Color = Color::GRAY;
+
+/// Marker component for de-spawning the outliner
+#[derive(Component)]
+struct OnOutliner;
+
+/// The node containing the rows, which is moved up and down to scroll the list
+#[derive(Component, Default)]
+struct OutlinerList {
+    position: f32,
+}
+
+/// A row in the outliner, which selects its object when clicked
+#[derive(Component)]
+struct OutlinerRow(Entity);
+
+/// System that spawns the outliner panel when entering the game
+fn setup_outliner(mut commands: Commands) {
+    commands
+        .spawn((
+            GameMarker,
+            OnOutliner,
+            PickableBlock,
+            NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Column,
+                    position_type: PositionType::Absolute,
+                    // NOTE: Positioned to the right of the tool panel
+                    top: Val::Px(10.),
+                    left: Val::Px(230.),
+                    width: Val::Px(200.),
+                    max_height: Val::Percent(MAX_HEIGHT_PERCENT),
+                    border: UiRect::all(Val::Px(1.)),
+                    padding: UiRect::all(Val::Px(10.)),
+                    ..default()
+                },
+                background_color: Color::BLACK.into(),
+                border_color: Color::WHITE.into(),
+                ..default()
+            },
+        ))
+        .with_children(|p| {
+            spawn_tool_panel_heading("Outliner", (), p);
+
+            // Clip the list so it can be scrolled
+            p.spawn(NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Column,
+                    overflow: Overflow::clip_y(),
+                    ..default()
+                },
+                ..default()
+            })
+            .with_children(|p| {
+                p.spawn((
+                    OutlinerList::default(),
+                    NodeBundle {
+                        style: Style {
+                            flex_direction: FlexDirection::Column,
+                            ..default()
+                        },
+                        ..default()
+                    },
+                ));
+            });
+        });
+}
+
+/// System that re-spawns the rows whenever objects are added, removed, renamed, re-parented, locked or hidden.
+///
+/// NOTE: Locked and hidden objects are still listed, so they can be selected and unlocked or shown again.
+#[allow(clippy::too_many_arguments)]
+#[allow(clippy::type_complexity)]
+fn rebuild_outliner_list(
+    mut commands: Commands,
+    list_query: Query<Entity, With<OutlinerList>>,
+    object_query: Query<
+        (
+            Entity,
+            Option<&Name>,
+            Option<&Parent>,
+            Has<Locked>,
+            Has<Hidden>,
+        ),
+        (With<Saveable>, WithoutLevelData),
+    >,
+    changed_query: Query<
+        (),
+        (
+            With<Saveable>,
+            WithoutLevelData,
+            Or<(
+                Added<Saveable>,
+                Changed<Name>,
+                Changed<Parent>,
+                Added<Locked>,
+                Added<Hidden>,
+            )>,
+        ),
+    >,
+    mut removed: RemovedComponents<Saveable>,
+    (mut removed_locked, mut removed_hidden): (
+        RemovedComponents<Locked>,
+        RemovedComponents<Hidden>,
+    ),
+    added_list_query: Query<(), Added<OutlinerList>>,
+) {
+    // NOTE: Read all removal events, so they don't trigger a rebuild in a later frame
+    let has_removed =
+        removed.read().count() + removed_locked.read().count() + removed_hidden.read().count() > 0;
+    if changed_query.is_empty() && !has_removed && added_list_query.is_empty() {
+        return;
+    }
+
+    // Group the objects by their parent, so attachables can be nested under the object they are attached to
+    let mut roots: Vec<(Entity, String)> = Vec::new();
+    let mut children: Vec<(Entity, Entity, String)> = Vec::new();
+    for (entity, name, parent, locked, hidden) in object_query.iter() {
+        let mut label = name.map_or_else(|| format!("Object {entity:?}"), |name| name.to_string());
+        if locked {
+            label.push_str(" (locked)");
+        }
+        if hidden {
+            label.push_str(" (hidden)");
+        }
+        match parent.filter(|parent| object_query.contains(parent.get())) {
+            Some(parent) => children.push((parent.get(), entity, label)),
+            None => roots.push((entity, label)),
+        }
+    }
+    // NOTE: Queries are not ordered, so sort the rows to keep the list stable
+    roots.sort_by(|(a, a_label), (b, b_label)| a_label.cmp(b_label).then(a.cmp(b)));
+    children.sort_by(|(_, a, a_label), (_, b, b_label)| a_label.cmp(b_label).then(a.cmp(b)));
+
+    for list in list_query.iter() {
+        commands.entity(list).despawn_descendants();
+        commands.entity(list).with_children(|p| {
+            for (entity, label) in roots.iter() {
+                spawn_rows(*entity, label, 0, &children, p);
+            }
+        });
+    }
+}
+
+/// Utility that spawns a row for an object, followed by rows for all of its attachables.
+fn spawn_rows(
+    entity: Entity,
+    label: &str,
+    depth: usize,
+    children: &[(Entity, Entity, String)],
+    child_builder: &mut ChildBuilder,
+) {
+    child_builder
+        .spawn((
+            OutlinerRow(entity),
+            ButtonBundle {
+                style: Style {
+                    padding: UiRect {
+                        left: Val::Px(5. + INDENT * depth as f32),
+                        right: Val::Px(5.),
+                        top: Val::Px(2.),
+                        bottom: Val::Px(2.),
+                    },
+                    ..default()
+                },
+                background_color: ROW_COLOR.into(),
+                ..default()
+            },
+        ))
+        .with_children(|p| {
+            p.spawn(TextBundle::from_section(
+                label,
+                TextStyle {
+                    font_size: FONT_SIZE_BASE,
+                    ..default()
+                },
+            ));
+        });
+
+    for (_, child, child_label) in children.iter().filter(|(parent, ..)| *parent == entity) {
+        spawn_rows(*child, child_label, depth + 1, children, child_builder);
+    }
+}
+
+/// System that highlights the rows of selected objects, and the row under the cursor.
+fn update_row_styles(
+    mut query: Query<(Ref<OutlinerRow>, Ref<Interaction>, &mut BackgroundColor)>,
+    selection: Res<Selection>,
+) {
+    let selection_changed = selection.is_changed();
+    for (row, interaction, mut background) in query.iter_mut() {
+        if !selection_changed && !row.is_added() && !interaction.is_changed() {
+            continue;
+        }
+        let color = if selection.contains(row.0) {
+            ROW_SELECTED_COLOR
+        } else if *interaction == Interaction::Hovered {
+            ROW_HOVER_COLOR
+        } else {
+            ROW_COLOR
+        };
+        *background = color.into();
+    }
+}
+
+/// System that selects objects when their row is clicked
+fn handle_row_interactions(
+    query: Query<(&OutlinerRow, &Interaction), Changed<Interaction>>,
+    input: ActionInput,
+    selection: Res<Selection>,
+    mut select_writer: EventWriter<SelectEvent>,
+) {
+    let is_modifier = input.pressed(&EditorAction::ToggleSelection);
+
+    for (row, interaction) in query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        if is_modifier {
+            // Toggle the object in the selection
+            let mut entities = selection.entities().to_vec();
+            if selection.contains(row.0) {
+                entities.retain(|selected| *selected != row.0);
+            } else {
+                entities.push(row.0);
+            }
+            select_writer.send(SelectEvent(entities));
+        } else {
+            select_writer.send(SelectEvent(vec![row.0]));
+        }
+    }
+}
+
+/// System that scrolls the list when scrolling the mouse over the outliner
+fn handle_mouse_scroll(
+    mut scroll_events: EventReader<MouseWheel>,
+    panel_query: Query<&RelativeCursorPosition, With<OnOutliner>>,
+    mut list_query: Query<(&mut OutlinerList, &mut Style, &Parent, &Node)>,
+    container_query: Query<&Node>,
+    mut scroll_captured: ResMut<ScrollCaptured>,
+) {
+    let scroll: f32 = scroll_events.read().map(|event| event.y).sum();
+    if scroll == 0. || !panel_query.iter().any(|cursor| cursor.mouse_over()) {
+        return;
+    }
+    // Stop the camera from zooming while scrolling the list
+    scroll_captured.0 = true;
+
+    for (mut list, mut style, parent, list_node) in list_query.iter_mut() {
+        let Ok(container_node) = container_query.get(parent.get()) else {
+            continue;
+        };
+        let max_scroll = (list_node.size().y - container_node.size().y).max(0.);
+        list.position = (list.position + scroll * SCROLL_SPEED).clamp(-max_scroll, 0.);
+        style.top = Val::Px(list.position);
+    }
+}
@@ -1,13 +1,26 @@
 use bevy::prelude::*;
 
+use duplicate_tool::prelude::ClipboardEvent;
+use editor::prelude::*;
+use game_effects::selected::Selection;
 use game_state::prelude::*;
 use save::prelude::*;
 
 use crate::config::SAVE_FILENAME;
 
 use super::{
-    failed_to_load_menu::FailedToLoadMenuPlugin, new_level::NewLevelPlugin,
-    tool_loader::ToolLoaderPlugin, tool_panel::ToolPanelPlugin,
+    bookmark_panel::BookmarkPanelPlugin,
+    failed_to_load_menu::FailedToLoadMenuPlugin,
+    input_loader::InputLoaderPlugin,
+    inspector::InspectorPlugin,
+    layer_panel::LayerPanelPlugin,
+    new_level::NewLevelPlugin,
+    outliner::OutlinerPlugin,
+    tool_loader::ToolLoaderPlugin,
+    tool_panel::ToolPanelPlugin,
+    unsaved_changes_dialog::{
+        DiscardChangesAction, DiscardChangesEvent, PendingDiscardAction, UnsavedChangesDialogPlugin,
+    },
 };
 
 /// Plugin responsible for in-game menus, ui, state transitions etc.
@@ -23,50 +36,128 @@ impl Plugin for GamePlugin {
         app.add_plugins((
             ToolPanelPlugin,
             ToolLoaderPlugin,
+            InputLoaderPlugin,
             NewLevelPlugin,
             FailedToLoadMenuPlugin,
+            UnsavedChangesDialogPlugin,
+            InspectorPlugin,
+            OutlinerPlugin,
+            LayerPanelPlugin,
+            BookmarkPanelPlugin,
         ))
-        .add_systems(Update, handle_keypress.run_if(in_state(PlayState::Active)));
+        .add_systems(
+            Update,
+            handle_keypress
+                .run_if(in_state(PlayState::Active))
+                .run_if(not(resource_exists::<PendingDiscardAction>())),
+        );
     }
 }
 
-/// System that handles key presses while in the game, namely Undo/Redo.
+/// System that handles key presses while in the game, namely Undo/Redo, saving, loading, the clipboard, and arranging,
+/// grouping, locking, hiding and framing the selected objects, and controlling the camera.
+///
+/// The keys for each action are configured in the `InputMap`.
+#[allow(clippy::too_many_arguments)]
 fn handle_keypress(
-    keys: Res<Input<KeyCode>>,
+    input: ActionInput,
     mut roll_back_writer: EventWriter<RollbackBackEvent>,
     mut roll_forward_writer: EventWriter<RollbackForwardEvent>,
     mut save_writer: EventWriter<SaveEvent>,
-    mut load_writer: EventWriter<LoadEvent>,
+    mut discard_writer: EventWriter<DiscardChangesEvent>,
+    mut clipboard_writer: EventWriter<ClipboardEvent>,
+    mut arrange_writer: EventWriter<ArrangeObjectsEvent>,
+    (mut group_writer, mut ungroup_writer): (
+        EventWriter<GroupObjects>,
+        EventWriter<UngroupObjects>,
+    ),
+    mut flag_writer: EventWriter<ToggleObjectFlag>,
+    (mut frame_writer, mut camera_writer): (EventWriter<FrameObjects>, EventWriter<CameraEvent>),
+    selection: Res<Selection>,
 ) {
-    let is_control = keys.pressed(KeyCode::ControlLeft) || keys.pressed(KeyCode::ControlRight);
-    let is_shift = keys.pressed(KeyCode::ShiftLeft) || keys.pressed(KeyCode::ShiftRight);
-
-    // Undo (Ctrl + Z)
-    if is_control && !is_shift && keys.just_pressed(KeyCode::Z) {
+    if input.just_pressed(&EditorAction::Undo) {
         roll_back_writer.send(RollbackBackEvent);
     }
-
-    // Redo (Ctrl + Y || Ctrl + Shift + Z)
-    if is_control
-        && ((is_shift && keys.just_pressed(KeyCode::Z))
-            || (!is_shift && keys.just_pressed(KeyCode::Y)))
-    {
+    if input.just_pressed(&EditorAction::Redo) {
         roll_forward_writer.send(RollbackForwardEvent);
     }
 
     // Save the level
-    if is_control && !is_shift && keys.just_pressed(KeyCode::S) {
+    if input.just_pressed(&EditorAction::Save) {
         save_writer.send(SaveEvent {
             filename: SAVE_FILENAME.to_string(),
             location: StorageLocation::Assets,
         });
     }
 
-    // Load the level
-    if is_control && !is_shift && keys.just_pressed(KeyCode::L) {
-        load_writer.send(LoadEvent {
-            filename: SAVE_FILENAME.to_string(),
-            location: StorageLocation::Assets,
-        });
+    // Load the level, asking for confirmation first if there are unsaved changes
+    if input.just_pressed(&EditorAction::Load) {
+        discard_writer.send(DiscardChangesEvent(DiscardChangesAction::LoadLevel));
+    }
+
+    // Copy, cut and paste
+    if input.just_pressed(&EditorAction::Copy) {
+        clipboard_writer.send(ClipboardEvent::Copy);
+    }
+    if input.just_pressed(&EditorAction::Cut) {
+        clipboard_writer.send(ClipboardEvent::Cut);
+    }
+    if input.just_pressed(&EditorAction::Paste) {
+        clipboard_writer.send(ClipboardEvent::Paste);
+    }
+
+    // Export the selection as RON text and import it again
+    if input.just_pressed(&EditorAction::ExportClipboard) {
+        clipboard_writer.send(ClipboardEvent::Export);
+    }
+    if input.just_pressed(&EditorAction::ImportClipboard) {
+        clipboard_writer.send(ClipboardEvent::Import);
+    }
+
+    // Align, distribute and drop the selected objects to the ground
+    for command in ArrangeCommand::ALL {
+        if input.just_pressed(&command.action()) && !selection.is_empty() {
+            arrange_writer.send(ArrangeObjectsEvent {
+                command,
+                entities: selection.entities().to_vec(),
+            });
+        }
+    }
+
+    // Group and ungroup the selected objects
+    if input.just_pressed(&EditorAction::Group) {
+        group_writer.send(GroupObjects(selection.entities().to_vec()));
+    }
+    if input.just_pressed(&EditorAction::Ungroup) {
+        ungroup_writer.send(UngroupObjects(selection.entities().to_vec()));
+    }
+
+    // Lock/unlock and hide/show the selected objects
+    for (action, flag) in [
+        (EditorAction::ToggleLocked, ObjectFlag::Locked),
+        (EditorAction::ToggleHidden, ObjectFlag::Hidden),
+    ] {
+        if input.just_pressed(&action) && !selection.is_empty() {
+            flag_writer.send(ToggleObjectFlag {
+                flag,
+                entities: selection.entities().to_vec(),
+            });
+        }
+    }
+
+    // Frame the selected objects, switch to the plan view, and store and recall camera bookmarks
+    if input.just_pressed(&EditorAction::FrameSelected) && !selection.is_empty() {
+        frame_writer.send(FrameObjects(selection.entities().to_vec()));
+    }
+    if input.just_pressed(&EditorAction::TogglePlanView) {
+        camera_writer.send(CameraEvent::TogglePlanView);
+    }
+    for slot in 1..=9 {
+        if input.just_pressed(&EditorAction::StoreBookmark(slot)) {
+            camera_writer.send(CameraEvent::StoreBookmark(slot));
+        }
+        if input.just_pressed(&EditorAction::RecallBookmark(slot)) {
+            camera_writer.send(CameraEvent::RecallBookmark(slot));
+        }
     }
 }
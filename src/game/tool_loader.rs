@@ -59,12 +59,28 @@ fn handle_tool_assets(
             // define the prefab tool config
             let prefab_config = PrefabConfig {
                 name: tool_def.name.clone(),
+                key: Some(tool_def.key.clone()),
                 scene: assets.load(scene_path),
                 tool_type: match &tool_def.tool_type {
                     PrefabToolAssetType::Attachable(config) => {
                         PrefabToolType::Attachable(PrefabAttachableConfig {
                             distance: config.distance,
                             forward: config.forward,
+                            rules: AttachRules {
+                                attach_to: config
+                                    .attach_to
+                                    .iter()
+                                    .map(|target| match target {
+                                        PrefabToolAssetAttachTarget::Component(type_path) => {
+                                            AttachTarget::Component(type_path.clone())
+                                        }
+                                        PrefabToolAssetAttachTarget::Tag(tag) => {
+                                            AttachTarget::Tag(tag.clone())
+                                        }
+                                    })
+                                    .collect(),
+                                max_per_parent: config.max_per_parent,
+                            },
                         })
                     }
                     PrefabToolAssetType::Object => PrefabToolType::Object,
@@ -74,6 +90,8 @@ fn handle_tool_assets(
                     min: scaling.min,
                     max: scaling.max,
                 }),
+                allow_overlap: tool_def.allow_overlap,
+                tags: tool_def.tags.clone(),
             };
 
             // Register the tool in the tool library
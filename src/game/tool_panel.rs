@@ -2,14 +2,17 @@ use bevy::prelude::*;
 
 use bevy_helpers::generic_systems::despawn_recursive_with;
 use editor::prelude::*;
+use game_effects::selected::Selection;
 use game_state::prelude::*;
 use save::{
-    prelude::{LoadEvent, RollbackBackEvent, RollbackForwardEvent, SaveEvent},
+    prelude::{RollbackBackEvent, RollbackForwardEvent, SaveEvent},
     types::StorageLocation,
 };
 
 use crate::{config::SAVE_FILENAME, widgets::*};
 
+use super::unsaved_changes_dialog::{DiscardChangesAction, DiscardChangesEvent};
+
 /// Plugin that handles the tool panel while in the game.
 pub struct ToolPanelPlugin;
 
@@ -36,6 +39,12 @@ enum ToolButtonAction {
     Redo,
     Save,
     Load,
+    Arrange(ArrangeCommand),
+    Group,
+    Ungroup,
+    ToggleFlag(ObjectFlag),
+    FrameSelected,
+    TogglePlanView,
 }
 
 /// System that spawns and handles the tool panel when in game
@@ -43,6 +52,7 @@ fn setup_tool_panel(
     mut commands: Commands,
     tool_library: Res<ToolLibrary>,
     button_style: Res<ToolButtonStyle>,
+    input_map: Res<InputMap>,
 ) {
     spawn_tool_panel(
         &mut commands,
@@ -54,7 +64,11 @@ fn setup_tool_panel(
             // Tool Buttons from the tool library
             for tool in tool_library.tools.iter() {
                 spawn_tool_button(
-                    &tool.name,
+                    button_label(
+                        &tool.name,
+                        &EditorAction::Tool(tool.key.clone()),
+                        &input_map,
+                    ),
                     ToolButtonAction::Tool(tool.tool.clone()),
                     &button_style,
                     false,
@@ -65,30 +79,89 @@ fn setup_tool_panel(
             // Extra Buttons
             spawn_tool_panel_heading("Undo/Redo", (), p);
             spawn_tool_button(
-                "Undo (CTRL + Z)",
+                button_label("Undo", &EditorAction::Undo, &input_map),
                 ToolButtonAction::Undo,
                 &button_style,
                 false,
                 p,
             );
             spawn_tool_button(
-                "Redo (CTRL + Y)",
+                button_label("Redo", &EditorAction::Redo, &input_map),
                 ToolButtonAction::Redo,
                 &button_style,
                 false,
                 p,
             );
+            spawn_tool_panel_heading("Arrange", (), p);
+            for command in ArrangeCommand::ALL {
+                spawn_tool_button(
+                    button_label(command.label(), &command.action(), &input_map),
+                    ToolButtonAction::Arrange(command),
+                    &button_style,
+                    false,
+                    p,
+                );
+            }
+            spawn_tool_button(
+                button_label("Group", &EditorAction::Group, &input_map),
+                ToolButtonAction::Group,
+                &button_style,
+                false,
+                p,
+            );
+            spawn_tool_button(
+                button_label("Ungroup", &EditorAction::Ungroup, &input_map),
+                ToolButtonAction::Ungroup,
+                &button_style,
+                false,
+                p,
+            );
+            spawn_tool_button(
+                button_label("Lock/Unlock", &EditorAction::ToggleLocked, &input_map),
+                ToolButtonAction::ToggleFlag(ObjectFlag::Locked),
+                &button_style,
+                false,
+                p,
+            );
+            spawn_tool_button(
+                button_label("Hide/Show", &EditorAction::ToggleHidden, &input_map),
+                ToolButtonAction::ToggleFlag(ObjectFlag::Hidden),
+                &button_style,
+                false,
+                p,
+            );
+            spawn_tool_panel_heading("Camera", (), p);
+            spawn_tool_panel_text(
+                "Right-drag to orbit, middle-drag to pan, scroll to zoom",
+                (),
+                p,
+            );
+            spawn_tool_panel_text(bookmark_hint(&input_map), (), p);
+            spawn_tool_button(
+                button_label("Frame Selected", &EditorAction::FrameSelected, &input_map),
+                ToolButtonAction::FrameSelected,
+                &button_style,
+                false,
+                p,
+            );
+            spawn_tool_button(
+                button_label("Plan View", &EditorAction::TogglePlanView, &input_map),
+                ToolButtonAction::TogglePlanView,
+                &button_style,
+                false,
+                p,
+            );
             spawn_tool_panel_heading("Save/Load", (), p);
             spawn_tool_panel_text(format!("Filename: assets/{SAVE_FILENAME}"), (), p);
             spawn_tool_button(
-                "Save (CTRL + S)",
+                button_label("Save", &EditorAction::Save, &input_map),
                 ToolButtonAction::Save,
                 &button_style,
                 false,
                 p,
             );
             spawn_tool_button(
-                "Load (CTRL + L)",
+                button_label("Load", &EditorAction::Load, &input_map),
                 ToolButtonAction::Load,
                 &button_style,
                 true,
@@ -98,7 +171,31 @@ fn setup_tool_panel(
     );
 }
 
+/// Utility that returns the label for a button, including the key bound to its action, e.g. "Undo (CTRL + Z)".
+fn button_label(name: &str, action: &EditorAction, input_map: &InputMap) -> String {
+    match input_map.label(action) {
+        Some(key) => format!("{name} ({key})"),
+        None => name.to_string(),
+    }
+}
+
+/// Utility that returns the hint describing how to store and recall camera bookmarks, using the key bindings of the
+/// first bookmark as an example.
+fn bookmark_hint(input_map: &InputMap) -> String {
+    let label = |action: EditorAction| {
+        input_map
+            .label(&action)
+            .unwrap_or_else(|| "unbound".to_string())
+    };
+    format!(
+        "Bookmark 1: {} to store, {} to recall",
+        label(EditorAction::StoreBookmark(1)),
+        label(EditorAction::RecallBookmark(1))
+    )
+}
+
 /// System that handles tool button click actions
+#[allow(clippy::too_many_arguments)]
 #[allow(clippy::type_complexity)]
 fn handle_button_interactions(
     mut query: Query<(&ToolButtonAction, &Interaction), Changed<Interaction>>,
@@ -106,7 +203,15 @@ fn handle_button_interactions(
     mut undo_writer: EventWriter<RollbackBackEvent>,
     mut redo_writer: EventWriter<RollbackForwardEvent>,
     mut save_writer: EventWriter<SaveEvent>,
-    mut load_writer: EventWriter<LoadEvent>,
+    mut discard_writer: EventWriter<DiscardChangesEvent>,
+    mut arrange_writer: EventWriter<ArrangeObjectsEvent>,
+    (mut group_writer, mut ungroup_writer): (
+        EventWriter<GroupObjects>,
+        EventWriter<UngroupObjects>,
+    ),
+    mut flag_writer: EventWriter<ToggleObjectFlag>,
+    (mut frame_writer, mut camera_writer): (EventWriter<FrameObjects>, EventWriter<CameraEvent>),
+    selection: Res<Selection>,
 ) {
     for (action, interaction) in query.iter_mut() {
         if *interaction == Interaction::Pressed {
@@ -121,10 +226,27 @@ fn handle_button_interactions(
                     filename: SAVE_FILENAME.to_string(),
                     location: StorageLocation::Assets,
                 }),
-                ToolButtonAction::Load => load_writer.send(LoadEvent {
-                    filename: SAVE_FILENAME.to_string(),
-                    location: StorageLocation::Assets,
+                ToolButtonAction::Load => {
+                    discard_writer.send(DiscardChangesEvent(DiscardChangesAction::LoadLevel))
+                }
+                ToolButtonAction::Arrange(command) => arrange_writer.send(ArrangeObjectsEvent {
+                    command: *command,
+                    entities: selection.entities().to_vec(),
+                }),
+                ToolButtonAction::Group => {
+                    group_writer.send(GroupObjects(selection.entities().to_vec()))
+                }
+                ToolButtonAction::Ungroup => {
+                    ungroup_writer.send(UngroupObjects(selection.entities().to_vec()))
+                }
+                ToolButtonAction::ToggleFlag(flag) => flag_writer.send(ToggleObjectFlag {
+                    flag: *flag,
+                    entities: selection.entities().to_vec(),
                 }),
+                ToolButtonAction::FrameSelected => {
+                    frame_writer.send(FrameObjects(selection.entities().to_vec()))
+                }
+                ToolButtonAction::TogglePlanView => camera_writer.send(CameraEvent::TogglePlanView),
             }
         }
     }
@@ -0,0 +1,215 @@
+use bevy::{app::AppExit, prelude::*, window::WindowCloseRequested};
+
+use bevy_helpers::generic_systems::despawn_recursive_with;
+use game_state::prelude::*;
+use save::prelude::*;
+
+use crate::{config::SAVE_FILENAME, widgets::*};
+
+/// Plugin that asks the user for confirmation before performing an action that would discard unsaved changes.
+///
+/// Actions that would lose work should send a `DiscardChangesEvent` instead of performing the action directly. If there
+/// are no unsaved changes the action is performed right away, otherwise a dialog is shown and the action is only
+/// performed once the user confirms it.
+///
+/// Transitions to `GameState::ExitGame` and `GameState::ReloadLevel` are intercepted automatically, so they can be
+/// triggered from anywhere without having to know about this plugin.
+pub struct UnsavedChangesDialogPlugin;
+
+impl Plugin for UnsavedChangesDialogPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<DiscardChangesEvent>()
+            .add_systems(
+                PreUpdate,
+                intercept_leave_game_transitions.run_if(in_state(GameState::Game)),
+            )
+            .add_systems(
+                Update,
+                (
+                    handle_window_close_requests.run_if(on_event::<WindowCloseRequested>()),
+                    handle_discard_changes_events.run_if(on_event::<DiscardChangesEvent>()),
+                    setup_dialog.run_if(resource_added::<PendingDiscardAction>()),
+                    despawn_recursive_with::<OnUnsavedChangesDialog>
+                        .run_if(resource_removed::<PendingDiscardAction>()),
+                    handle_button_interactions.run_if(resource_exists::<PendingDiscardAction>()),
+                ),
+            );
+    }
+}
+
+/// An action that would discard unsaved changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DiscardChangesAction {
+    /// Load the level from `SAVE_FILENAME`
+    LoadLevel,
+    /// Transition to a `GameState` that de-spawns the level
+    SetGameState(GameState),
+    /// Exit the app
+    Quit,
+}
+
+/// Event used to request an action that would discard unsaved changes.
+#[derive(Event, Debug)]
+pub(crate) struct DiscardChangesEvent(pub DiscardChangesAction);
+
+/// Resource that exists while the dialog is waiting for the user to confirm an action.
+#[derive(Resource, Debug)]
+pub(crate) struct PendingDiscardAction(DiscardChangesAction);
+
+/// Marker component for de-spawning the dialog
+#[derive(Component)]
+struct OnUnsavedChangesDialog;
+
+/// A button in the dialog
+#[derive(Component)]
+enum DialogButtonAction {
+    Discard,
+    Cancel,
+}
+
+/// System that stops transitions that would de-spawn the level while there are unsaved changes, and asks for
+/// confirmation instead.
+///
+/// NOTE: This runs in `PreUpdate` so it can reset `NextState` before the `StateTransition` schedule applies it.
+fn intercept_leave_game_transitions(
+    mut next_game_state: ResMut<NextState<GameState>>,
+    unsaved_changes: Res<UnsavedChanges>,
+    mut discard_writer: EventWriter<DiscardChangesEvent>,
+) {
+    if !unsaved_changes.is_dirty() {
+        return;
+    }
+    if let Some(state @ (GameState::ExitGame | GameState::ReloadLevel)) = next_game_state.0 {
+        next_game_state.0 = None;
+        discard_writer.send(DiscardChangesEvent(DiscardChangesAction::SetGameState(
+            state,
+        )));
+    }
+}
+
+/// System that asks for confirmation when the window is closed.
+///
+/// IMPORTANT: This requires `WindowPlugin::close_when_requested` to be false, otherwise the window closes before we get
+/// a chance to ask.
+fn handle_window_close_requests(
+    mut events: EventReader<WindowCloseRequested>,
+    mut discard_writer: EventWriter<DiscardChangesEvent>,
+) {
+    if events.read().last().is_some() {
+        discard_writer.send(DiscardChangesEvent(DiscardChangesAction::Quit));
+    }
+}
+
+/// System that performs the requested action right away if there are no unsaved changes, or asks for confirmation
+/// otherwise.
+#[allow(clippy::too_many_arguments)]
+fn handle_discard_changes_events(
+    mut commands: Commands,
+    mut events: EventReader<DiscardChangesEvent>,
+    pending_action: Option<Res<PendingDiscardAction>>,
+    mut unsaved_changes: ResMut<UnsavedChanges>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+    mut load_writer: EventWriter<LoadEvent>,
+    mut app_exit_writer: EventWriter<AppExit>,
+) {
+    // Only the latest request matters, and we ignore requests while the dialog is already open
+    let Some(DiscardChangesEvent(action)) = events.read().last() else {
+        return;
+    };
+    if pending_action.is_some() {
+        return;
+    }
+
+    if unsaved_changes.is_dirty() {
+        info!("[Unsaved Changes] ==> asking for confirmation: {action:?}");
+        commands.insert_resource(PendingDiscardAction(*action));
+    } else {
+        perform_action(
+            *action,
+            &mut unsaved_changes,
+            &mut next_game_state,
+            &mut load_writer,
+            &mut app_exit_writer,
+        );
+    }
+}
+
+/// System that spawns the dialog when an action is waiting for confirmation
+fn setup_dialog(
+    mut commands: Commands,
+    pending_action: Res<PendingDiscardAction>,
+    button_style: Res<ToolButtonStyle>,
+) {
+    let discard_text = match pending_action.0 {
+        DiscardChangesAction::LoadLevel => "Discard and Load",
+        DiscardChangesAction::SetGameState(_) => "Discard and Leave",
+        DiscardChangesAction::Quit => "Discard and Quit",
+    };
+
+    spawn_dialog(
+        &mut commands,
+        (OnUnsavedChangesDialog, PickableBlock),
+        |p| {
+            spawn_menu_screen_heading("Unsaved Changes", (), p);
+            spawn_menu_screen_text("The garden has changes that have not been saved.", (), p);
+            spawn_tool_button(
+                discard_text,
+                DialogButtonAction::Discard,
+                &button_style,
+                false,
+                p,
+            );
+            spawn_tool_button("Cancel", DialogButtonAction::Cancel, &button_style, true, p);
+        },
+    );
+}
+
+/// System that handles click events for buttons in the dialog
+#[allow(clippy::type_complexity)]
+fn handle_button_interactions(
+    mut commands: Commands,
+    query: Query<(&DialogButtonAction, &Interaction), Changed<Interaction>>,
+    pending_action: Res<PendingDiscardAction>,
+    mut unsaved_changes: ResMut<UnsavedChanges>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+    mut load_writer: EventWriter<LoadEvent>,
+    mut app_exit_writer: EventWriter<AppExit>,
+) {
+    for (action, interaction) in query.iter() {
+        if *interaction == Interaction::Pressed {
+            if let DialogButtonAction::Discard = action {
+                perform_action(
+                    pending_action.0,
+                    &mut unsaved_changes,
+                    &mut next_game_state,
+                    &mut load_writer,
+                    &mut app_exit_writer,
+                );
+            }
+            commands.remove_resource::<PendingDiscardAction>();
+        }
+    }
+}
+
+/// Utility that performs an action that discards unsaved changes.
+fn perform_action(
+    action: DiscardChangesAction,
+    unsaved_changes: &mut UnsavedChanges,
+    next_game_state: &mut NextState<GameState>,
+    load_writer: &mut EventWriter<LoadEvent>,
+    app_exit_writer: &mut EventWriter<AppExit>,
+) {
+    info!("[Unsaved Changes] ==> performing action: {action:?}");
+    match action {
+        DiscardChangesAction::LoadLevel => load_writer.send(LoadEvent {
+            filename: SAVE_FILENAME.to_string(),
+            location: StorageLocation::Assets,
+        }),
+        DiscardChangesAction::SetGameState(state) => {
+            // Mark the changes as discarded so the transition is not intercepted again
+            unsaved_changes.discard();
+            next_game_state.set(state);
+        }
+        DiscardChangesAction::Quit => app_exit_writer.send(AppExit),
+    }
+}
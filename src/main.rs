@@ -7,7 +7,7 @@ use bevy_mod_raycast::prelude::*;
 use apple::prelude::ApplePlugin;
 use background::prelude::BackgroundPlugin;
 use bush::prelude::BushPlugin;
-use duplicate_tool::prelude::DuplicateToolPlugin;
+use duplicate_tool::prelude::{ClipboardPlugin, DuplicateToolPlugin};
 use editor::prelude::EditorPlugin;
 use flower::prelude::FlowerPlugin;
 use game::GamePlugin;
@@ -48,6 +48,9 @@ fn main() {
                         prevent_default_event_handling: true,
                         ..default()
                     }),
+                    // Closing the window is handled by `UnsavedChangesDialogPlugin`, so we can ask for confirmation
+                    // if there are unsaved changes
+                    close_when_requested: false,
                     ..default()
                 })
                 .build(),
@@ -67,6 +70,7 @@ fn main() {
             PointerToolPlugin,
             BushPlugin,
             DuplicateToolPlugin,
+            ClipboardPlugin,
         ))
         // first-party plugins for in-game menus (tool panel UI and failed-to-load menu), spawning the game and
         // loading the tool assets.
@@ -1,7 +1,10 @@
+mod dialog;
 mod menu_screen;
 mod plugin;
 mod text_heading;
 mod tool_button;
 mod tool_panel;
 
-pub use self::{menu_screen::*, plugin::*, text_heading::*, tool_button::*, tool_panel::*};
+pub use self::{
+    dialog::*, menu_screen::*, plugin::*, text_heading::*, tool_button::*, tool_panel::*,
+};
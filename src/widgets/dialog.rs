@@ -0,0 +1,44 @@
+use bevy::prelude::*;
+
+/// Utility that spawns a modal dialog node.
+///
+/// The dialog covers the whole screen with a translucent overlay and is drawn above all other UI nodes.
+pub fn spawn_dialog(
+    commands: &mut Commands,
+    bundle: impl Bundle,
+    children: impl FnOnce(&mut ChildBuilder),
+) {
+    commands
+        .spawn((
+            bundle,
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    top: Val::Px(0.),
+                    left: Val::Px(0.),
+                    bottom: Val::Px(0.),
+                    right: Val::Px(0.),
+                    ..default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.5).into(),
+                z_index: ZIndex::Global(100),
+                ..default()
+            },
+        ))
+        .with_children(|p| {
+            p.spawn(NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Column,
+                    border: UiRect::all(Val::Px(1.)),
+                    padding: UiRect::all(Val::Px(10.)),
+                    ..default()
+                },
+                background_color: Color::BLACK.into(),
+                border_color: Color::WHITE.into(),
+                ..default()
+            })
+            .with_children(|p| children(p));
+        });
+}